@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+/// Months of rent the annual income should cover to pass screening, i.e. rent at most 1/3 of the monthly income.
+pub const SCREENING_MONTHS: i32 = 36;
+
+/// Income of the people renting. 0 for unknown.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(default)]
+pub struct Income {
+    /// Before tax (額面), as screened by landlords and guarantee companies.
+    pub annual_gross: i32,
+    /// Paid into the bank a month (手取り).
+    pub monthly_take_home: i32,
+    /// Largest share of the monthly gross income to spend on the effective rent, in percent.
+    pub budget_percent: f64,
+}
+
+impl Default for Income {
+    fn default() -> Self {
+        Income { annual_gross: 0, monthly_take_home: 0, budget_percent: 100.0 / 3.0 }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Affordability {
+    /// Effective rent in percent of the monthly gross income.
+    pub rent_of_gross: f64,
+    /// Effective rent in percent of the take-home pay, if given.
+    pub rent_of_take_home: Option<f64>,
+    /// Effective cost of living in percent of the monthly gross income.
+    pub living_of_gross: f64,
+    pub living_of_take_home: Option<f64>,
+    /// The advertised rent and management fee are at most 1/36 of the annual income.
+    pub passes_screening: bool,
+    /// Highest advertised rent and management fee likely to pass screening.
+    pub screening_max_rent: i32,
+    pub budget_percent: f64,
+    /// Highest effective rent within `budget_percent` of the monthly gross income.
+    pub max_rent: i32,
+}
+
+fn percent(amount: i32, of: f64) -> f64 {
+    amount as f64 / of * 100.0
+}
+
+impl Income {
+    /// `None` without an annual income. `advertised` is the rent and management fee of the listing.
+    pub fn report(&self, advertised: i32, monthly: i32, living_monthly: i32) -> Option<Affordability> {
+        if self.annual_gross <= 0 {
+            return None;
+        }
+        let gross = self.annual_gross as f64 / 12.0;
+        let take_home = Some(self.monthly_take_home as f64).filter(|t| *t > 0.0);
+        Some(Affordability {
+            rent_of_gross: percent(monthly, gross),
+            rent_of_take_home: take_home.map(|t| percent(monthly, t)),
+            living_of_gross: percent(living_monthly, gross),
+            living_of_take_home: take_home.map(|t| percent(living_monthly, t)),
+            passes_screening: advertised as i64 * SCREENING_MONTHS as i64 <= self.annual_gross as i64,
+            screening_max_rent: self.annual_gross / SCREENING_MONTHS,
+            budget_percent: self.budget_percent,
+            max_rent: (gross * self.budget_percent / 100.0).floor() as i32,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::affordability::Income;
+
+    #[test]
+    fn report() {
+        assert_eq!(Income::default().report(80000, 90000, 100000), None);
+
+        let income = Income { annual_gross: 3_600_000, monthly_take_home: 240_000, ..Default::default() };
+        let r = income.report(100000, 90000, 120000).unwrap();
+        assert!(r.passes_screening);
+        assert_eq!(r.screening_max_rent, 100000);
+        assert_eq!(r.max_rent, 100000);
+        assert!((r.rent_of_gross - 30.0).abs() < 1e-9);
+        assert_eq!(r.living_of_take_home, Some(50.0));
+        assert!(!income.report(100001, 90000, 120000).unwrap().passes_screening);
+
+        let income = Income { monthly_take_home: 0, budget_percent: 25.0, ..income };
+        let r = income.report(100000, 90000, 120000).unwrap();
+        assert_eq!(r.rent_of_take_home, None);
+        assert_eq!(r.max_rent, 75000);
+    }
+}
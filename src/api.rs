@@ -0,0 +1,620 @@
+use std::convert::TryFrom;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+
+use crate::InputError;
+use crate::affordability::Income;
+use crate::area::Area;
+use crate::calculator::{FreeRent, RentCalculator, RentElements, RentItem};
+use crate::components::Component;
+use crate::constants::{ContractType, ItemKind, MovingKind, RunningKind, UnitId};
+use crate::date::Date;
+use crate::decision::Renewal;
+use crate::home_office::ItemShare;
+use crate::listing::parse_listing;
+use crate::moving::CurrentHome;
+use crate::ranking::{RankKey, rank};
+use crate::schedule::{Change, TermChange};
+use crate::split::{Share, Split, SplitRule};
+use crate::utils::to_js;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_RENT_RESULT: &'static str = r#"
+export interface RentResult {
+    /** Months of the lease. Renewals are counted with this. */
+    leasePeriod: number;
+    leaseDays: number;
+    /** The lease was given in days. `monthly` figures are then for 30 days. */
+    countedInDays: boolean;
+    total: number;
+    monthly: number;
+    daily: number;
+    originalTotal: number;
+    originalMonthly: number;
+    /** One entry per contract term, the first one being the initial contract. */
+    terms: TermSummary[];
+    contractType: "Ordinary" | "FixedTerm" | "FixedTermNoRecontract";
+    /** The planned stay is longer than a fixed term that cannot be re-contracted. */
+    exceedsTerm: boolean;
+    /** `YYYY-MM-DD`, when either date is given. The other one is derived from the planned stay. */
+    moveIn: string | null;
+    moveOut: string | null;
+    /** Prorated rent and management fee of the move-in month. */
+    firstMonth: number | null;
+    /** Prorated rent and management fee of the move-out month. */
+    lastMonth: number | null;
+    /** Paid at move-in when the next month is due up front as well, free rent taken off. */
+    moveInPayment: number | null;
+    /** Already subtracted from `total`. */
+    freeRentDiscount: number;
+    /** Moving out before the minimum stay cancels the free rent. */
+    freeRentClawedBack: boolean;
+    /** Annual percentage. The figures below equal `total` and `monthly` when 0. */
+    discountRate: number;
+    /** Value today of all payments, including `depositCost`. */
+    presentValue: number;
+    /** Level payment at the start of each month worth `presentValue` today. */
+    levelMonthly: number;
+    /** Value today of the returns lost by leaving the deposit until it is paid back. */
+    depositCost: number;
+    /** Days the rent of the current apartment is paid after moving in. */
+    overlapDays: number;
+    /** Rent of the current apartment for `overlapDays`. Part of `total` only if `overlapInTotal`. */
+    overlapCost: number;
+    overlapInTotal: boolean;
+    /** `YYYY-MM-DD`, latest day to give notice, when the current apartment's move-out date is given. */
+    noticeBy: string | null;
+    /** Costs of moving in, apart from the property's fees. */
+    movingCosts: MovingItem[];
+    movingTotal: number;
+    /** `movingTotal` is part of `total`, spread over the lease in `monthly`. */
+    movingInTotal: boolean;
+    /** Utilities and internet a month, besides what the rent includes. */
+    runningMonthly: number;
+    /** `total` plus the running costs over the stay. */
+    livingTotal: number;
+    /** The effective cost of living here, per month (or per 30 days) like `monthly`. */
+    livingMonthly: number;
+    area: { squareMeters: number, jo: number };
+    occupants: number;
+    /** `monthly` per ㎡, when the area is given. */
+    perSquareMeter: number | null;
+    /** `monthly` per 畳, when given. */
+    perJo: number | null;
+    perPerson: number;
+    /** The costs against the income, when it is given. */
+    affordability: Affordability | null;
+    /** What the employer and the employee pay, when there is a subsidy. */
+    subsidy: SubsidyResult | null;
+    /** Business and personal parts of the costs by year, when some are used for business. */
+    homeOffice: HomeOfficeReport | null;
+    /** Renting against buying, when a purchase is compared. */
+    rentVsBuy: RentVsBuy | null;
+    /** What each person sharing the property pays, when they are named. */
+    shares: PersonShare[];
+    /** Renewing the current apartment against moving here, when a planning horizon is set. */
+    decision: Decision | null;
+    /** The cheapest sequence of renewing and moving over the same horizon. */
+    plan: Plan | null;
+    /** The horizon ends before the current contract does, so there is nothing to compare. */
+    horizonTooShort: boolean;
+}
+
+export interface MovingItem {
+    kind: "Movers" | "Curtains" | "Lighting" | "AirConditioner" | "InternetSetup" | "Disposal" | "Other";
+    value: number;
+}
+
+export interface Affordability {
+    /** Effective rent in percent of the monthly gross income. */
+    rentOfGross: number;
+    /** Effective rent in percent of the take-home pay, if given. */
+    rentOfTakeHome: number | null;
+    /** Effective cost of living in percent of the monthly gross income. */
+    livingOfGross: number;
+    livingOfTakeHome: number | null;
+    /** The advertised rent and management fee are at most 1/36 of the annual income. */
+    passesScreening: boolean;
+    /** Highest advertised rent and management fee likely to pass screening. */
+    screeningMaxRent: number;
+    budgetPercent: number;
+    /** Highest effective rent within `budgetPercent` of the monthly gross income. */
+    maxRent: number;
+}
+
+export interface SubsidyResult {
+    /** The rent and management fee are within the limit. */
+    eligible: boolean;
+    rentLimit: number;
+    allowanceTotal: number;
+    /** The employer's part of the rent over the stay. */
+    rentShareTotal: number;
+    paidItemsTotal: number;
+    /** Everything the employer pays over the stay. */
+    employerTotal: number;
+    /** What the employee pays of the total. */
+    netTotal: number;
+    /** `netTotal` per month, the employee's effective rent. */
+    netMonthly: number;
+}
+
+export interface ItemExpense {
+    item: ItemKind;
+    percent: number;
+    paid: number;
+    business: number;
+    personal: number;
+    /** Some payment is amortized as a deferred asset. */
+    deferred: boolean;
+}
+
+export interface ExpenseYear {
+    year: number;
+    paid: number;
+    /** Business part of what was paid in the year. */
+    business: number;
+    personal: number;
+    /** Business expenses of the year: what was paid, except deferred payments, plus their amortization. */
+    deductible: number;
+    /** Part of `deductible` amortizing deferred payments. */
+    amortized: number;
+}
+
+export interface HomeOfficeReport {
+    /** The years are calendar years. Otherwise they count the years of the lease from 1. */
+    calendar: boolean;
+    items: ItemExpense[];
+    /** The running costs not in the rent, at the common business share. */
+    utilities: number;
+    utilitiesBusiness: number;
+    years: ExpenseYear[];
+}
+
+export interface YearCost {
+    year: number;
+    /** What was paid, with the costs of moving out at the end of the year. */
+    rent: number;
+    /** What was paid, less what selling would bring after paying the loan back. */
+    buy: number;
+}
+
+export interface Variant {
+    mortgageRate: number;
+    resaleValue: number;
+    buyTotal: number;
+    crossoverYear: number | null;
+}
+
+export interface RentVsBuy {
+    horizonYears: number;
+    monthlyPayment: number;
+    rentTotal: number;
+    buyTotal: number;
+    cheaper: "Rent" | "Buy";
+    /** Cumulative costs at the end of each year. */
+    years: YearCost[];
+    /** First year after which buying has cost less, if any. */
+    crossoverYear: number | null;
+    /** The buying cost at other mortgage rates. */
+    byRate: Variant[];
+    /** The buying cost at other resale values. */
+    byResale: Variant[];
+}
+
+export interface PersonShare {
+    name: string;
+    /** Paid when moving in, the deposit included. */
+    upfront: number;
+    /** Paid every month from the second month on. */
+    monthly: number;
+    /** Over the whole stay, without the deposit. */
+    total: number;
+    /** `total` per month, like `monthly` of the result. */
+    effective: number;
+}
+
+export interface Decision {
+    horizon: number;
+    /** Rent and renewal fees of the current apartment over the horizon. */
+    stayCost: number;
+    /** Rent of the current apartment until its contract ends, moving costs and this property's total after that. */
+    moveCost: number;
+    cheaper: "Stay" | "Move";
+    /** How much the cheaper path saves. */
+    saving: number;
+}
+
+export interface Plan {
+    horizon: number;
+    total: number;
+    /** One entry per contract term, in order. */
+    segments: Segment[];
+}
+
+export interface Segment {
+    /** Month the segment starts from, 0 being now. */
+    start: number;
+    months: number;
+    /** `Replacement` is a lease of this property, or of a similar one after moving again. */
+    home: "Current" | "Replacement";
+    /** The segment starts with a move, whose costs are included in `cost`. */
+    moved: boolean;
+    cost: number;
+}
+
+export interface TermSummary {
+    months: number;
+    rent: number;
+    managementFee: number;
+    /** Renewal or re-contract fee paid when the term starts. Always 0 for the first term. */
+    renewalFee: number;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "RentResult")]
+    pub type JsRentResult;
+}
+
+/// Input conditions of one property, usable without the built-in UI.
+#[wasm_bindgen]
+pub struct Scenario {
+    elements: RentElements,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<RentElements> for Scenario {
+    fn from(elements: RentElements) -> Self {
+        Scenario { elements }
+    }
+}
+
+#[wasm_bindgen]
+impl Scenario {
+    /// Creates a scenario filled with the same defaults as the form.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Scenario {
+        RentElements::default().into()
+    }
+
+    /// Restores a scenario from the JSON shown in the export area.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Scenario, JsValue> {
+        serde_json::from_str::<RentElements>(json)
+            .map(Scenario::from)
+            .map_err(|e| JsValue::from(js_sys::Error::new(&format!("{}", e))))
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.elements.to_json_string()
+    }
+
+    pub fn value(&self, kind: ItemKind) -> i32 {
+        self.elements.item(kind).value
+    }
+
+    #[wasm_bindgen(js_name = setValue)]
+    pub fn set_value(&mut self, kind: ItemKind, value: i32) {
+        self.elements.item_mut(kind).value = value;
+    }
+
+    pub fn unit(&self, kind: ItemKind) -> UnitId {
+        self.elements.item(kind).unit
+    }
+
+    /// Fails if `unit` is not selectable for `kind` on the form.
+    #[wasm_bindgen(js_name = setUnit)]
+    pub fn set_unit(&mut self, kind: ItemKind, unit: UnitId) -> Result<(), JsValue> {
+        if !kind.units().contains(&unit) {
+            return Err(InputError::UnsupportedUnit(kind, unit).into());
+        }
+        self.elements.item_mut(kind).unit = unit;
+        Ok(())
+    }
+
+    /// Months between charges when the unit is `UnitId.EveryNMonthsYen`.
+    pub fn interval(&self, kind: ItemKind) -> i32 {
+        self.elements.item(kind).interval
+    }
+
+    #[wasm_bindgen(js_name = setInterval)]
+    pub fn set_interval(&mut self, kind: ItemKind, months: i32) {
+        self.elements.item_mut(kind).interval = months;
+    }
+
+    /// Adds a charge of `kind` on top of its main value, e.g. a yearly part of the guarantee fee.
+    /// Fails if `unit` is not selectable for `kind` on the form.
+    #[wasm_bindgen(js_name = addComponent)]
+    pub fn add_component(&mut self, kind: ItemKind, value: i32, unit: UnitId) -> Result<(), JsValue> {
+        if !kind.units().contains(&unit) {
+            return Err(InputError::UnsupportedUnit(kind, unit).into());
+        }
+        self.elements.components.push(Component { item: kind, charge: RentItem::new(value, unit) });
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = clearComponents)]
+    pub fn clear_components(&mut self, kind: ItemKind) {
+        self.elements.components.retain(|c| c.item != kind);
+    }
+
+    /// Sets the conditions of the free rent months.
+    /// `start` is the month they start from, 0 being the first month of the lease.
+    /// `min_stay` is the stay in months below which the free rent is paid back, 0 for none.
+    #[wasm_bindgen(js_name = setFreeRent)]
+    pub fn set_free_rent(&mut self, covers_management_fee: bool, start: i32, min_stay: i32) {
+        self.elements.free_rent = FreeRent { covers_management_fee, start, min_stay };
+    }
+
+    /// Annual percentage used for the present value, 0 for none.
+    #[wasm_bindgen(js_name = discountRate)]
+    pub fn discount_rate(&self) -> f64 {
+        self.elements.discount_rate
+    }
+
+    #[wasm_bindgen(js_name = setDiscountRate)]
+    pub fn set_discount_rate(&mut self, percent: f64) {
+        self.elements.discount_rate = percent;
+    }
+
+    /// Sets the apartment being left. `rent` includes the management fee, 0 for none.
+    /// `move_out` takes `YYYY-MM-DD`, or `undefined` to assume notice is given when the new lease starts.
+    /// `in_total` counts the overlapping rent in the new total rather than as a moving cost.
+    #[wasm_bindgen(js_name = setCurrentHome)]
+    pub fn set_current_home(&mut self, rent: i32, notice_months: i32, move_out: Option<String>,
+                            in_total: bool) -> Result<(), JsValue> {
+        let move_out = move_out.map(Date::try_from).transpose()?;
+        self.elements.current_home = CurrentHome { rent, notice_months, move_out, in_total };
+        Ok(())
+    }
+
+    /// Sets the cost of moving in for `kind`, 0 to remove it.
+    #[wasm_bindgen(js_name = setMovingCost)]
+    pub fn set_moving_cost(&mut self, kind: MovingKind, value: i32) {
+        self.elements.moving.set(kind, value);
+    }
+
+    /// Whether the moving costs are part of the total, spread over the lease. Defaults to true.
+    #[wasm_bindgen(js_name = setMovingCostsInTotal)]
+    pub fn set_moving_costs_in_total(&mut self, in_total: bool) {
+        self.elements.moving.in_total = in_total;
+    }
+
+    /// Sets the monthly estimate of `kind`. `included` tells the rent already covers it.
+    #[wasm_bindgen(js_name = setRunningCost)]
+    pub fn set_running_cost(&mut self, kind: RunningKind, value: i32, included: bool) {
+        self.elements.running.set(kind, value, included);
+    }
+
+    /// Sets the floor area in ㎡ and the tatami mats (畳) of the rooms, 0 for unknown.
+    #[wasm_bindgen(js_name = setArea)]
+    pub fn set_area(&mut self, square_meters: f64, jo: f64) {
+        self.elements.area = Area { square_meters, jo };
+    }
+
+    #[wasm_bindgen(js_name = setOccupants)]
+    pub fn set_occupants(&mut self, occupants: i32) {
+        self.elements.occupants = occupants;
+    }
+
+    /// Sets the income for the affordability report, 0 for unknown.
+    /// `budget_percent` is the largest share of the monthly gross income for the effective rent.
+    #[wasm_bindgen(js_name = setIncome)]
+    pub fn set_income(&mut self, annual_gross: i32, monthly_take_home: i32, budget_percent: f64) {
+        self.elements.income = Income { annual_gross, monthly_take_home, budget_percent };
+    }
+
+    /// Sets the employer's housing allowance a month and, for a corporate lease, the part of the rent
+    /// it pays, at most `rent_cap` a month. Nothing is paid when the rent and management fee
+    /// exceed `rent_limit`. 0 turns each off.
+    #[wasm_bindgen(js_name = setSubsidy)]
+    pub fn set_subsidy(&mut self, allowance: i32, rent_percent: f64, rent_cap: i32, rent_limit: i32) {
+        let subsidy = &mut self.elements.subsidy;
+        subsidy.allowance = allowance;
+        subsidy.rent_percent = rent_percent;
+        subsidy.rent_cap = rent_cap;
+        subsidy.rent_limit = rent_limit;
+    }
+
+    /// Sets whether the employer pays `item` in full.
+    #[wasm_bindgen(js_name = setEmployerPaid)]
+    pub fn set_employer_paid(&mut self, item: ItemKind, paid: bool) {
+        let items = &mut self.elements.subsidy.paid_items;
+        items.retain(|i| *i != item);
+        if paid {
+            items.push(item);
+        }
+    }
+
+    /// Sets the business share of the costs in percent, for every item without its own.
+    #[wasm_bindgen(js_name = setBusinessPercent)]
+    pub fn set_business_percent(&mut self, percent: f64) {
+        self.elements.home_office.percent = percent;
+    }
+
+    /// Sets the business share of `item` in percent, or the common one again with `None`.
+    #[wasm_bindgen(js_name = setItemBusinessPercent)]
+    pub fn set_item_business_percent(&mut self, item: ItemKind, percent: Option<f64>) {
+        let items = &mut self.elements.home_office.items;
+        items.retain(|i| i.item != item);
+        if let Some(percent) = percent {
+            items.push(ItemShare { item, percent });
+        }
+    }
+
+    /// Sets the home to buy, for the rent-vs-buy comparison over `horizon_years` years (0 turns it off).
+    /// `resale_value` is the expected sale price at the end, after the selling costs.
+    #[wasm_bindgen(js_name = setPurchase)]
+    pub fn set_purchase(&mut self, price: i32, down_payment: i32, closing_costs: i32, resale_value: i32,
+                        horizon_years: i32) {
+        let purchase = &mut self.elements.purchase;
+        purchase.price = price;
+        purchase.down_payment = down_payment;
+        purchase.closing_costs = closing_costs;
+        purchase.resale_value = resale_value;
+        purchase.horizon_years = horizon_years;
+    }
+
+    /// Sets the mortgage of the home to buy, `rate` a year in percent.
+    #[wasm_bindgen(js_name = setMortgage)]
+    pub fn set_mortgage(&mut self, rate: f64, years: i32) {
+        self.elements.purchase.mortgage_rate = rate;
+        self.elements.purchase.mortgage_years = years;
+    }
+
+    /// Sets the costs of owning the home to buy: monthly fees and the yearly property tax.
+    #[wasm_bindgen(js_name = setOwnershipCosts)]
+    pub fn set_ownership_costs(&mut self, management_fee: i32, repair_reserve: i32, property_tax: i32) {
+        let purchase = &mut self.elements.purchase;
+        purchase.management_fee = management_fee;
+        purchase.repair_reserve = repair_reserve;
+        purchase.property_tax = property_tax;
+    }
+
+    /// Names the people sharing the property. Items without a rule are split equally between them.
+    #[wasm_bindgen(js_name = setPeople)]
+    pub fn set_people(&mut self, names: Vec<String>) {
+        self.elements.split.people = names;
+    }
+
+    /// `person` pays `percent` of what the fixed amounts of `item` leave, weighed against the other percentages.
+    #[wasm_bindgen(js_name = addSplitByPercent)]
+    pub fn add_split_by_percent(&mut self, item: ItemKind, person: String, percent: f64) {
+        self.elements.split.rules.push(SplitRule { item, person, share: Share::Percent(percent) });
+    }
+
+    /// `person` pays `value` yen of each charge of `item`, e.g. every month for the rent.
+    #[wasm_bindgen(js_name = addSplitFixed)]
+    pub fn add_split_fixed(&mut self, item: ItemKind, person: String, value: i32) {
+        self.elements.split.rules.push(SplitRule { item, person, share: Share::Fixed(value) });
+    }
+
+    #[wasm_bindgen(js_name = clearSplit)]
+    pub fn clear_split(&mut self) {
+        self.elements.split = Split::default();
+    }
+
+    /// Sets the coming renewal of the current apartment, whose rent is set by `setCurrentHome`.
+    /// Moving costs are those set by `setMovingCost`.
+    /// The result then compares renewing with moving here when the contract ends, over `horizon` months,
+    /// and finds the cheapest sequence of renewing and moving over the same horizon.
+    #[wasm_bindgen(js_name = setRenewal)]
+    pub fn set_renewal(&mut self, months_left: i32, renewal_fee: i32, renewed_rent: i32,
+                       contract_period: i32, horizon: i32) {
+        self.elements.renewal = Renewal {
+            months_left, renewal_fee, renewed_rent, contract_period, horizon,
+        };
+    }
+
+    #[wasm_bindgen(js_name = contractType)]
+    pub fn contract_type(&self) -> ContractType {
+        self.elements.contract_type
+    }
+
+    #[wasm_bindgen(js_name = setContractType)]
+    pub fn set_contract_type(&mut self, contract_type: ContractType) {
+        self.elements.contract_type = contract_type;
+    }
+
+    /// `YYYY-MM-DD`, if set.
+    #[wasm_bindgen(js_name = moveIn)]
+    pub fn move_in(&self) -> Option<String> {
+        self.elements.move_in.map(String::from)
+    }
+
+    /// Takes `YYYY-MM-DD`, or `undefined` to clear the date.
+    #[wasm_bindgen(js_name = setMoveIn)]
+    pub fn set_move_in(&mut self, date: Option<String>) -> Result<(), JsValue> {
+        self.elements.move_in = date.map(Date::try_from).transpose()?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = moveOut)]
+    pub fn move_out(&self) -> Option<String> {
+        self.elements.move_out.map(String::from)
+    }
+
+    #[wasm_bindgen(js_name = setMoveOut)]
+    pub fn set_move_out(&mut self, date: Option<String>) -> Result<(), JsValue> {
+        self.elements.move_out = date.map(Date::try_from).transpose()?;
+        Ok(())
+    }
+
+    /// Sets `item` to `value` yen from the `renewal`-th renewal (1 = the first one) onward.
+    #[wasm_bindgen(js_name = addChangeTo)]
+    pub fn add_change_to(&mut self, item: ItemKind, renewal: i32, value: i32) {
+        self.elements.schedule.push(TermChange { renewal, item, change: Change::Set(value) });
+    }
+
+    /// Changes `item` by `percent` from the `renewal`-th renewal (1 = the first one) onward.
+    #[wasm_bindgen(js_name = addChangeByPercent)]
+    pub fn add_change_by_percent(&mut self, item: ItemKind, renewal: i32, percent: f64) {
+        self.elements.schedule.push(TermChange { renewal, item, change: Change::Percent(percent) });
+    }
+
+    #[wasm_bindgen(js_name = clearChanges)]
+    pub fn clear_changes(&mut self) {
+        self.elements.schedule.clear();
+    }
+
+    /// Overwrites the items found in text copied from a listing page.
+    /// Returns the items that were recognized.
+    #[wasm_bindgen(js_name = importListing)]
+    pub fn import_listing(&mut self, text: &str) -> Vec<ItemKind> {
+        let import = parse_listing(text, &self.elements);
+        self.elements = import.elements;
+        import.recognized
+    }
+
+    pub fn calculate(&self) -> Result<JsRentResult, JsValue> {
+        let result = RentCalculator {}.calc(self.elements.clone())?;
+        Ok(to_js(&result)?.unchecked_into())
+    }
+}
+
+/// Several scenarios to compare, such as the candidates of a search.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Comparison {
+    scenarios: Vec<RentElements>,
+}
+
+#[wasm_bindgen]
+impl Comparison {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Comparison {
+        Comparison::default()
+    }
+
+    /// Adds a copy of `scenario`. Later changes to it are not seen.
+    pub fn add(&mut self, scenario: &Scenario) {
+        self.scenarios.push(scenario.elements.clone());
+    }
+
+    pub fn len(&self) -> u32 {
+        self.scenarios.len() as u32
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.scenarios.is_empty()
+    }
+
+    /// Indices of the scenarios from the cheapest by `key`. Those without the figure, like the rent
+    /// per ㎡ without an area, come last. Fails if a scenario cannot be calculated.
+    pub fn rank(&self, key: RankKey) -> Result<Vec<u32>, JsValue> {
+        let results = self.scenarios
+            .iter()
+            .map(|e| RentCalculator {}.calc(e.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rank(&results, key).into_iter().map(|i| i as u32).collect())
+    }
+}
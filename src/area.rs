@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Floor area of the property. 0 for unknown.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Area {
+    /// Of the whole unit, as given in listings (専有面積).
+    /// `square_meters` is still read from scenarios saved before the rename.
+    #[serde(alias = "square_meters")]
+    pub square_meters: f64,
+    /// Tatami mats (畳) of the rooms, usually leaving out the kitchen and bathroom.
+    pub jo: f64,
+}
+
+/// `monthly` divided by `size`, or `None` when the size is unknown.
+pub fn per_unit(monthly: i32, size: f64) -> Option<i32> {
+    if size > 0.0 { Some((monthly as f64 / size).round() as i32) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::area::{Area, per_unit};
+
+    #[test]
+    fn per_unit_of_area() {
+        assert_eq!(per_unit(80000, 25.0), Some(3200));
+        assert_eq!(per_unit(80000, 6.5), Some(12308));
+        assert_eq!(per_unit(80000, 0.0), None);
+    }
+
+    #[test]
+    fn area_keys() {
+        let area = Area { square_meters: 25.5, jo: 8.0 };
+        let json = serde_json::to_value(area).unwrap();
+        assert_eq!(json["squareMeters"], 25.5);
+        assert_eq!(json["jo"], 8.0);
+        let old: Area = serde_json::from_str(r#"{"square_meters": 25.5, "jo": 8.0}"#).unwrap();
+        assert_eq!(old, area);
+    }
+}
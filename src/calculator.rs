@@ -1,20 +1,118 @@
-use std::cmp;
-
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use wasm_bindgen::prelude::*;
-use web_sys::Document;
-use std::convert::TryInto;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlInputElement};
+use std::convert::{TryFrom, TryInto};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 
-use crate::constants::{InputId, UnitId};
-use crate::utils::fetch_value;
+use crate::CalcError;
+use crate::affordability::{Affordability, Income};
+use crate::area::{Area, per_unit};
+use crate::cash_flow::{cash_flows, deposit_cost, level_payment, monthly_rate, present_value};
+use crate::components::{Component, read_components};
+use crate::constants::{ContractType, InputId, ItemKind, MovingKind, RunningKind, UnitId};
+use crate::date::{Date, Stay, prorate};
+use crate::decision::{Decision, Plan, Renewal, compare, plan};
+use crate::home_office::{HomeOffice, HomeOfficeReport, home_office_report, read_item_percents};
+use crate::moving::{CurrentHome, MovingCosts, MovingItem};
+use crate::purchase::{Purchase, RentVsBuy, rent_vs_buy};
+use crate::running_costs::RunningCosts;
+use crate::split::{PersonShare, Split, read_split, split_costs};
+use crate::subsidy::{Subsidy, SubsidyResult, read_paid_items, subsidize};
+use crate::utils::{fetch_value, peek_value};
+use crate::schedule::{TermChange, read_schedule, term_lengths, term_values};
+use crate::yen::Yen;
 
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct RentResult {
     pub lease_period: i32,
+    pub lease_days: i32,
+    /// The lease was given in days, and monthly figures are for 30 days.
+    pub counted_in_days: bool,
     pub total: i32,
     pub monthly: i32,
+    pub daily: i32,
     pub original_total: i32,
     pub original_monthly: i32,
+    pub terms: Vec<TermSummary>,
+    pub contract_type: ContractType,
+    /// The planned stay is longer than a fixed term that cannot be extended.
+    pub exceeds_term: bool,
+    pub move_in: Option<Date>,
+    pub move_out: Option<Date>,
+    /// Rent and management fee for the days lived in the move-in month, when dates are given.
+    pub first_month: Option<i32>,
+    /// Same for the move-out month. 0 when moving out in the move-in month.
+    pub last_month: Option<i32>,
+    /// Paid at move-in when the next month is due up front as well: `first_month` and
+    /// the rent and management fee in force the month after, less free rent.
+    pub move_in_payment: Option<i32>,
+    /// Already subtracted from `total`.
+    pub free_rent_discount: i32,
+    /// Moving out before the minimum stay cancels the free rent.
+    pub free_rent_clawed_back: bool,
+    pub discount_rate: f64,
+    /// Value today of all payments, including `deposit_cost`.
+    pub present_value: i32,
+    /// Level monthly payment worth `present_value` today, to compare with `monthly`.
+    pub level_monthly: i32,
+    /// Value today of the returns lost by leaving the deposit until it is paid back.
+    pub deposit_cost: i32,
+    /// Days the rent of the current apartment is paid after moving in.
+    pub overlap_days: i32,
+    /// Rent of the current apartment for `overlap_days`. In `total` only if so chosen.
+    pub overlap_cost: i32,
+    pub overlap_in_total: bool,
+    /// Latest day to give notice to the current apartment, when its move-out date is given.
+    pub notice_by: Option<Date>,
+    /// Costs of moving in, shown apart from the property's fees.
+    pub moving_costs: Vec<MovingItem>,
+    pub moving_total: i32,
+    /// `moving_total` is part of `total`, spread over the lease in `monthly`.
+    pub moving_in_total: bool,
+    /// Utilities and internet a month, besides what the rent includes.
+    pub running_monthly: i32,
+    /// `total` plus the running costs over the stay.
+    pub living_total: i32,
+    /// The effective cost of living here, per month (or per 30 days) like `monthly`.
+    pub living_monthly: i32,
+    pub area: Area,
+    pub occupants: i32,
+    /// `monthly` per ㎡, when the area is given.
+    pub per_square_meter: Option<i32>,
+    /// `monthly` per 畳, when given.
+    pub per_jo: Option<i32>,
+    pub per_person: i32,
+    /// What each person sharing the property pays, when they are named.
+    pub shares: Vec<PersonShare>,
+    /// The costs against the income, when it is given.
+    pub affordability: Option<Affordability>,
+    /// What the employer and the employee pay, when there is a subsidy.
+    pub subsidy: Option<SubsidyResult>,
+    /// Business and personal parts of the costs by year, when some are used for business.
+    pub home_office: Option<HomeOfficeReport>,
+    /// Renting against buying, when a purchase is compared.
+    pub rent_vs_buy: Option<RentVsBuy>,
+    /// Renewing the current apartment against moving here, when a planning horizon is given.
+    pub decision: Option<Decision>,
+    /// The cheapest sequence of renewing and moving over the same horizon.
+    pub plan: Option<Plan>,
+    /// The horizon ends before the current contract does, so there is nothing to compare.
+    pub horizon_too_short: bool,
+}
+
+/// Amounts in force during one contract term.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TermSummary {
+    pub months: i32,
+    pub rent: i32,
+    pub management_fee: i32,
+    /// Renewal or re-contract fee paid when the term starts. Always 0 for the first term.
+    pub renewal_fee: i32,
 }
 
 
@@ -22,24 +120,108 @@ pub struct RentResult {
 pub struct RentItem {
     pub value: i32,
     pub unit: UnitId,
+    /// Months between charges for `UnitId::EveryNMonthsYen`.
+    #[serde(default = "default_interval")]
+    pub interval: i32,
+}
+
+fn default_interval() -> i32 {
+    12
 }
 
 impl RentItem {
-    fn get(&self, lease_period: i32, contract_period: i32) -> i32 {
+    pub fn new(value: i32, unit: UnitId) -> Self {
+        RentItem { value, unit, interval: default_interval() }
+    }
+
+    /// Total over the lease, with the value unchanged at renewals.
+    pub fn get(&self, lease_period: i32, contract_period: i32) -> i32 {
+        self.get_days(lease_period * DAYS_PER_MONTH, contract_period)
+    }
+
+    /// Same as `get`, for a lease of `lease_days` days.
+    pub fn get_days(&self, lease_days: i32, contract_period: i32) -> i32 {
+        let terms = term_lengths(months_of(lease_days), contract_period);
+        self.get_in_terms(&terms, &vec![self.value; terms.len()], &vec![0; terms.len()], lease_days)
+    }
+
+    /// Total over the contract terms lasting `terms` months each,
+    /// where `values` and `rents` are the value and the rent in force during each term,
+    /// and `lease_days` is the length of the whole lease.
+    pub fn get_in_terms(&self, terms: &[i32], values: &[i32], rents: &[i32], lease_days: i32) -> i32 {
+        let renewals = values.iter().zip(rents).skip(1);
         match self.unit {
-            UnitId::MonthlyYen => self.value * lease_period,
-            UnitId::OneShotYen => self.value,
-            UnitId::EveryContractYen => self.value * (1 + (lease_period - 1) / contract_period),
-            UnitId::EveryContractUpdateYen => cmp::max(0, self.value * ((lease_period - 1) / contract_period)),
-            UnitId::Month => self.value,
+            UnitId::MonthlyYen => terms.iter().zip(values).map(|(m, v)| m * v).sum(),
+            UnitId::OneShotYen => values[0],
+            UnitId::EveryContractYen => values.iter().sum(),
+            UnitId::EveryContractUpdateYen => renewals.map(|(v, _)| v).sum(),
+            UnitId::UpdateRentPercent => renewals.map(|(v, r)| r * v / 100).sum(),
+            UnitId::DailyYen => count_days(terms, lease_days, 1).iter().zip(values).map(|(d, v)| d * v).sum(),
+            UnitId::WeeklyYen => count_days(terms, lease_days, 7).iter().zip(values).map(|(w, v)| w * v).sum(),
+            UnitId::YearlyYen => count_months(terms, 12).iter().zip(values).map(|(y, v)| y * v).sum(),
+            UnitId::EveryNMonthsYen => {
+                count_months(terms, self.interval.max(1)).iter().zip(values).map(|(n, v)| n * v).sum()
+            }
+            UnitId::Month | UnitId::Day => values[0],
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Months in `RentResult` and in day-based leases are 30 days long.
+pub const DAYS_PER_MONTH: i32 = 30;
+
+/// Months started during `days` days.
+fn months_of(days: i32) -> i32 {
+    (days + DAYS_PER_MONTH - 1).div_euclid(DAYS_PER_MONTH)
+}
+
+/// Periods of `period` days started in each term, out of `lease_days` days in total.
+/// The last term ends with the lease, so it may be shorter than its months.
+fn count_days(terms: &[i32], lease_days: i32, period: i32) -> Vec<i32> {
+    count_started(terms, DAYS_PER_MONTH, lease_days, period)
+}
+
+/// Periods of `period` months started in each term, counted from the start of the lease.
+fn count_months(terms: &[i32], period: i32) -> Vec<i32> {
+    count_started(terms, 1, terms.iter().sum(), period)
+}
+
+/// Periods of `period` started in each term, where a month is `per_month` long
+/// and the whole lease is `total` long.
+fn count_started(terms: &[i32], per_month: i32, total: i32, period: i32) -> Vec<i32> {
+    let started = |at: i32| (at + period - 1).div_euclid(period);
+    let mut start = 0;
+    terms
+        .iter()
+        .enumerate()
+        .map(|(t, months)| {
+            let end = if t + 1 == terms.len() { total } else { (start + months * per_month).min(total) };
+            let count = started(end) - started(start);
+            start = end;
+            count
+        })
+        .collect()
+}
+
+/// Conditions of the free rent months.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct FreeRent {
+    /// The management fee is free as well as the rent.
+    pub covers_management_fee: bool,
+    /// Month of the lease the free months start from, 0 being the first one.
+    pub start: i32,
+    /// The free rent must be paid back when moving out before this many months. 0 for none.
+    pub min_stay: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
 pub struct RentElements {
     pub rent: RentItem,
     pub management_fee: RentItem,
+    /// Paid back when moving out, so only its opportunity cost counts.
+    pub deposit: RentItem,
     pub free_rent_period: RentItem,
     pub guarantee_fee: RentItem,
     pub support_fee: RentItem,
@@ -53,139 +235,562 @@ pub struct RentElements {
     pub key_change_fee: RentItem,
     pub cleaning_fee: RentItem,
     pub contract_update_fee: RentItem,
+    pub recontract_fee: RentItem,
     pub contract_period: RentItem,
     pub lease_period: RentItem,
+    pub contract_type: ContractType,
+    pub free_rent: FreeRent,
+    /// Annual percentage used to discount future payments. 0 treats all yen equally.
+    pub discount_rate: f64,
+    /// When set, monthly items are paid by the day in the first and last months,
+    /// and `lease_period` is derived from the dates.
+    pub move_in: Option<Date>,
+    pub move_out: Option<Date>,
+    pub schedule: Vec<TermChange>,
+    /// Charges added to the items, such as the yearly part of a guarantee fee.
+    pub components: Vec<Component>,
+    pub current_home: CurrentHome,
+    pub renewal: Renewal,
+    pub moving: MovingCosts,
+    pub running: RunningCosts,
+    pub area: Area,
+    /// People living in the property, sharing the rent.
+    pub occupants: i32,
+    pub split: Split,
+    pub income: Income,
+    pub subsidy: Subsidy,
+    pub home_office: HomeOffice,
+    pub purchase: Purchase,
+}
+
+impl Default for RentElements {
+    fn default() -> Self {
+        let yen = |value: i32, kind: ItemKind| RentItem::new(value, kind.units()[0]);
+        Self {
+            rent: yen(50000, ItemKind::Rent),
+            management_fee: yen(2000, ItemKind::ManagementFee),
+            deposit: yen(50000, ItemKind::Deposit),
+            free_rent_period: yen(0, ItemKind::FreeRentPeriod),
+            guarantee_fee: yen(0, ItemKind::GuaranteeFee),
+            support_fee: yen(0, ItemKind::SupportFee),
+            association_membership_fee: yen(0, ItemKind::AssociationMembershipFee),
+            key_money: yen(50000, ItemKind::KeyMoney),
+            brokerage_fee: yen(50000, ItemKind::BrokerageFee),
+            guarantee_administrative_fee: yen(0, ItemKind::GuaranteeAdministrativeFee),
+            insurance_fee: yen(10000, ItemKind::InsuranceFee),
+            bicycle_space_fee: yen(3000, ItemKind::BicycleSpaceFee),
+            car_space_fee: yen(0, ItemKind::CarSpaceFee),
+            key_change_fee: yen(5000, ItemKind::KeyChangeFee),
+            cleaning_fee: yen(0, ItemKind::CleaningFee),
+            contract_update_fee: yen(0, ItemKind::ContractUpdateFee),
+            recontract_fee: yen(0, ItemKind::RecontractFee),
+            contract_period: yen(24, ItemKind::ContractPeriod),
+            lease_period: yen(24, ItemKind::LeasePeriod),
+            contract_type: ContractType::default(),
+            free_rent: FreeRent::default(),
+            discount_rate: 0.0,
+            current_home: CurrentHome::default(),
+            renewal: Renewal::default(),
+            moving: MovingCosts::default(),
+            running: RunningCosts::default(),
+            area: Area::default(),
+            occupants: 1,
+            split: Split::default(),
+            income: Income::default(),
+            subsidy: Subsidy::default(),
+            home_office: HomeOffice::default(),
+            purchase: Purchase::default(),
+            move_in: None,
+            move_out: None,
+            schedule: vec![],
+            components: vec![],
+        }
+    }
 }
 
 impl RentElements {
+    pub fn item(&self, kind: ItemKind) -> &RentItem {
+        match kind {
+            ItemKind::Rent => &self.rent,
+            ItemKind::ManagementFee => &self.management_fee,
+            ItemKind::Deposit => &self.deposit,
+            ItemKind::KeyMoney => &self.key_money,
+            ItemKind::BrokerageFee => &self.brokerage_fee,
+            ItemKind::FreeRentPeriod => &self.free_rent_period,
+            ItemKind::GuaranteeFee => &self.guarantee_fee,
+            ItemKind::SupportFee => &self.support_fee,
+            ItemKind::AssociationMembershipFee => &self.association_membership_fee,
+            ItemKind::GuaranteeAdministrativeFee => &self.guarantee_administrative_fee,
+            ItemKind::InsuranceFee => &self.insurance_fee,
+            ItemKind::BicycleSpaceFee => &self.bicycle_space_fee,
+            ItemKind::CarSpaceFee => &self.car_space_fee,
+            ItemKind::KeyChangeFee => &self.key_change_fee,
+            ItemKind::CleaningFee => &self.cleaning_fee,
+            ItemKind::ContractUpdateFee => &self.contract_update_fee,
+            ItemKind::RecontractFee => &self.recontract_fee,
+            ItemKind::ContractPeriod => &self.contract_period,
+            ItemKind::LeasePeriod => &self.lease_period,
+        }
+    }
+
+    pub fn item_mut(&mut self, kind: ItemKind) -> &mut RentItem {
+        match kind {
+            ItemKind::Rent => &mut self.rent,
+            ItemKind::ManagementFee => &mut self.management_fee,
+            ItemKind::Deposit => &mut self.deposit,
+            ItemKind::KeyMoney => &mut self.key_money,
+            ItemKind::BrokerageFee => &mut self.brokerage_fee,
+            ItemKind::FreeRentPeriod => &mut self.free_rent_period,
+            ItemKind::GuaranteeFee => &mut self.guarantee_fee,
+            ItemKind::SupportFee => &mut self.support_fee,
+            ItemKind::AssociationMembershipFee => &mut self.association_membership_fee,
+            ItemKind::GuaranteeAdministrativeFee => &mut self.guarantee_administrative_fee,
+            ItemKind::InsuranceFee => &mut self.insurance_fee,
+            ItemKind::BicycleSpaceFee => &mut self.bicycle_space_fee,
+            ItemKind::CarSpaceFee => &mut self.car_space_fee,
+            ItemKind::KeyChangeFee => &mut self.key_change_fee,
+            ItemKind::CleaningFee => &mut self.cleaning_fee,
+            ItemKind::ContractUpdateFee => &mut self.contract_update_fee,
+            ItemKind::RecontractFee => &mut self.recontract_fee,
+            ItemKind::ContractPeriod => &mut self.contract_period,
+            ItemKind::LeasePeriod => &mut self.lease_period,
+        }
+    }
+
+    /// Total of the item and its components over the lease, following the schedule of changes.
+    /// Monthly charges are prorated by the day when the dates are given.
+    pub fn item_total(&self, kind: ItemKind, lease_period: i32, contract_period: i32) -> i32 {
+        let terms = term_lengths(lease_period, contract_period);
+        let values = self.term_values(kind, terms.len());
+        let rents = self.term_values(ItemKind::Rent, terms.len());
+        let components: i32 = self
+            .components
+            .iter()
+            .filter(|c| c.item == kind)
+            .map(|c| self.charge_total(&c.charge, &terms, &vec![c.charge.value; terms.len()], &rents))
+            .sum();
+        self.charge_total(self.item(kind), &terms, &values, &rents) + components
+    }
+
+    pub fn charge_total(&self, charge: &RentItem, terms: &[i32], values: &[i32], rents: &[i32]) -> i32 {
+        let lease_days = self.lease_days();
+        let total = charge.get_in_terms(terms, values, rents, lease_days);
+        if charge.unit != UnitId::MonthlyYen {
+            return total;
+        }
+        let (first, last) = (values[0], values[values.len() - 1]);
+        match self.stay() {
+            Some(stay) => total + stay.adjustment(first, last),
+            // the last month is paid by the day when the lease is counted in days
+            None => {
+                let lease_months: i32 = terms.iter().sum();
+                total + prorate(last, lease_days - lease_months * DAYS_PER_MONTH, DAYS_PER_MONTH)
+            }
+        }
+    }
+
+    /// Items paid over the lease. The deposit is paid back, so it is not one of them.
+    pub fn cost_items(&self) -> Vec<ItemKind> {
+        let mut items = vec![
+            ItemKind::Rent,
+            ItemKind::ManagementFee,
+            ItemKind::GuaranteeFee,
+            ItemKind::SupportFee,
+            ItemKind::AssociationMembershipFee,
+            ItemKind::KeyMoney,
+            ItemKind::BrokerageFee,
+            ItemKind::GuaranteeAdministrativeFee,
+            ItemKind::InsuranceFee,
+            ItemKind::BicycleSpaceFee,
+            ItemKind::CarSpaceFee,
+            ItemKind::KeyChangeFee,
+            ItemKind::CleaningFee,
+        ];
+        items.extend(self.contract_type.extension_fee());
+        items
+    }
+
+    /// Amount of the monthly item `kind` for the `month`-th month of the lease (0 being the first one),
+    /// prorated in the move-in month when the dates are given.
+    pub fn month_amount(&self, kind: ItemKind, month: i32, lease_period: i32, contract_period: i32) -> i32 {
+        let terms = term_lengths(lease_period, contract_period).len();
+        let term = ((month / contract_period) as usize).min(terms - 1);
+        let value = self.term_values(kind, terms)[term];
+        let full = match self.item(kind).unit {
+            UnitId::MonthlyYen => value,
+            UnitId::DailyYen => value * DAYS_PER_MONTH,
+            UnitId::WeeklyYen => prorate(value, DAYS_PER_MONTH, 7),
+            _ => 0,
+        };
+        match self.stay() {
+            Some(stay) if month == 0 => stay.first_month(full),
+            _ => full,
+        }
+    }
+
+    /// Rent and management fee of the month after move-in, none of which is paid in free months.
+    pub fn next_month_amount(&self, lease_period: i32, contract_period: i32) -> i32 {
+        let start = self.free_rent.start.max(0);
+        let free = !self.free_rent_clawed_back(lease_period)
+            && (start..start + self.free_rent_period.value.max(0)).contains(&1);
+        [ItemKind::Rent, ItemKind::ManagementFee]
+            .iter()
+            .filter(|kind| !(free && (**kind == ItemKind::Rent || self.free_rent.covers_management_fee)))
+            .map(|kind| self.month_amount(*kind, 1, lease_period, contract_period))
+            .sum()
+    }
+
+    /// The free rent has to be paid back since the stay is shorter than the minimum.
+    pub fn free_rent_clawed_back(&self, lease_period: i32) -> bool {
+        self.free_rent_period.value > 0 && lease_period < self.free_rent.min_stay
+    }
+
+    /// What the free months save over the lease. Months beyond the lease save nothing.
+    pub fn free_rent_discount(&self, lease_period: i32, contract_period: i32) -> i32 {
+        if self.free_rent_clawed_back(lease_period) {
+            return 0;
+        }
+        let start = self.free_rent.start.max(0);
+        let amount = |kind: ItemKind, month: i32| self.month_amount(kind, month, lease_period, contract_period);
+        (start..start + self.free_rent_period.value.max(0))
+            .filter(|m| *m < lease_period)
+            .map(|m| {
+                amount(ItemKind::Rent, m)
+                    + if self.free_rent.covers_management_fee { amount(ItemKind::ManagementFee, m) } else { 0 }
+            })
+            .sum()
+    }
+
+    pub fn counted_in_days(&self) -> bool {
+        self.lease_period.unit == UnitId::Day
+    }
+
+    pub fn stay(&self) -> Option<Stay> {
+        Stay::new(self.move_in, self.move_out, self.lease_period.value, self.counted_in_days())
+    }
+
+    /// Rent of the current apartment paid after moving in.
+    pub fn overlap_cost(&self) -> i32 {
+        self.current_home.overlap_cost(self.stay().map(|s| s.move_in))
+    }
+
+    /// Months of the stay, from the dates if given. Renewals are based on this.
+    pub fn lease_months(&self) -> i32 {
+        match self.stay() {
+            Some(stay) => stay.lease_months(),
+            None if self.counted_in_days() => months_of(self.lease_period.value),
+            None => self.lease_period.value,
+        }
+    }
+
+    /// `sum` over the stay per month. A month is 30 days when the lease is counted in days.
+    pub fn per_month(&self, sum: i32) -> i32 {
+        if self.counted_in_days() {
+            (sum as i64 * DAYS_PER_MONTH as i64 / self.lease_days() as i64) as i32
+        } else {
+            sum / self.lease_months()
+        }
+    }
+
+    /// A monthly amount paid over the whole stay, by the day when counted in days.
+    pub fn over_stay(&self, monthly: i32) -> i32 {
+        if self.counted_in_days() {
+            (monthly as i64 * self.lease_days() as i64 / DAYS_PER_MONTH as i64) as i32
+        } else {
+            monthly * self.lease_months()
+        }
+    }
+
+    /// Days of the stay, from the dates if given. A month counts as 30 days otherwise.
+    pub fn lease_days(&self) -> i32 {
+        match self.stay() {
+            Some(stay) => stay.days(),
+            None if self.counted_in_days() => self.lease_period.value,
+            None => self.lease_period.value * DAYS_PER_MONTH,
+        }
+    }
+
+    /// Value of the item in force during each of `terms` contract terms.
+    pub fn term_values(&self, kind: ItemKind, terms: usize) -> Vec<i32> {
+        term_values(self.item(kind).value, kind, &self.schedule, terms)
+    }
+
     pub fn to_json_string(&self) -> String {
         format!("{}", json!(self))
     }
 
-    pub fn build(document: &Document) -> Result<Self, JsValue> {
-        Ok(Self {
-            rent: RentItem {
-                value: fetch_value::<i32>(&document, InputId::Rent)?,
-                unit: fetch_value::<i32>(&document, InputId::RentUnit)?.try_into()?,
-            },
-            management_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::ManagementFee)?,
-                unit: fetch_value::<i32>(&document, InputId::ManagementFeeUnit)?.try_into()?,
-            },
-            free_rent_period: RentItem {
-                value: fetch_value::<i32>(&document, InputId::FreeRentPeriod)?,
-                unit: fetch_value::<i32>(&document, InputId::FreeRentPeriodUnit)?.try_into()?,
-            },
-            guarantee_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::GuaranteeFee)?,
-                unit: fetch_value::<i32>(&document, InputId::GuaranteeFeeUnit)?.try_into()?,
-            },
-            support_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::SupportFee)?,
-                unit: fetch_value::<i32>(&document, InputId::SupportFeeUnit)?.try_into()?,
-            },
-            association_membership_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::AssociationMembershipFee)?,
-                unit: fetch_value::<i32>(&document, InputId::AssociationMembershipFeeUnit)?.try_into()?,
-            },
-            key_money: RentItem {
-                value: fetch_value::<i32>(&document, InputId::KeyMoney)?,
-                unit: fetch_value::<i32>(&document, InputId::KeyMoneyUnit)?.try_into()?,
-            },
-            brokerage_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::BrokerageFee)?,
-                unit: fetch_value::<i32>(&document, InputId::BrokerageFeeUnit)?.try_into()?,
-            },
-            guarantee_administrative_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::GuaranteeAdministrativeFee)?,
-                unit: fetch_value::<i32>(&document, InputId::GuaranteeAdministrativeFeeUnit)?.try_into()?,
-            },
-            insurance_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::InsuranceFee)?,
-                unit: fetch_value::<i32>(&document, InputId::InsuranceFeeUnit)?.try_into()?,
-            },
-            bicycle_space_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::BicycleSpaceFee)?,
-                unit: fetch_value::<i32>(&document, InputId::BicycleSpaceFeeUnit)?.try_into()?,
-            },
-            car_space_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::CarSpaceFee)?,
-                unit: fetch_value::<i32>(&document, InputId::CarSpaceFeeUnit)?.try_into()?,
-            },
-            key_change_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::KeyChangeFee)?,
-                unit: fetch_value::<i32>(&document, InputId::KeyChangeFeeUnit)?.try_into()?,
-            },
-            cleaning_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::CleaningFee)?,
-                unit: fetch_value::<i32>(&document, InputId::CleaningFeeUnit)?.try_into()?,
-            },
-            contract_update_fee: RentItem {
-                value: fetch_value::<i32>(&document, InputId::ContractUpdateFee)?,
-                unit: fetch_value::<i32>(&document, InputId::ContractUpdateFeeUnit)?.try_into()?,
-            },
-            contract_period: RentItem {
-                value: fetch_value::<i32>(&document, InputId::ContractPeriod)?,
-                unit: fetch_value::<i32>(&document, InputId::ContractPeriodUnit)?.try_into()?,
-            },
-            lease_period: RentItem {
-                value: fetch_value::<i32>(&document, InputId::LeasePeriod)?,
-                unit: fetch_value::<i32>(&document, InputId::LeasePeriodUnit)?.try_into()?,
-            },
-        })
+    /// Reads the items on the form. Items not on the form keep the value in `base`.
+    /// Validation messages are shown only when `report` is set.
+    pub fn build(document: &Document, base: &RentElements, report: bool) -> Result<Self, JsValue> {
+        fn fetch<T: FromStr>(document: &Document, id: InputId, report: bool) -> Result<T, JsValue> {
+            if report { fetch_value::<T>(document, id) } else { peek_value::<T>(document, id) }
+        }
+
+        let mut e = base.clone();
+        for kind in ItemKind::iter() {
+            if document.get_element_by_id(&kind.input_id().to_string()).is_none() {
+                continue;
+            }
+            *e.item_mut(kind) = RentItem {
+                value: if kind.is_yen() {
+                    fetch::<Yen>(document, kind.input_id(), report)?.0
+                } else {
+                    fetch::<i32>(document, kind.input_id(), report)?
+                },
+                unit: fetch::<i32>(document, kind.unit_input_id(), report)?.try_into()?,
+                interval: match kind.interval_input_id() {
+                    Some(id) => fetch::<i32>(document, id, report)?,
+                    None => e.item(kind).interval,
+                },
+            };
+        }
+        if document.get_element_by_id(&InputId::ContractType.to_string()).is_some() {
+            e.contract_type = fetch::<i32>(document, InputId::ContractType, report)?.try_into()?;
+        }
+        for (id, date) in [(InputId::MoveIn, &mut e.move_in), (InputId::MoveOut, &mut e.move_out)] {
+            if let Some(value) = read_date(document, id) {
+                *date = value;
+            }
+        }
+        for kind in ItemKind::iter() {
+            if let Some(components) = read_components(document, kind) {
+                e.components.retain(|c| c.item != kind);
+                e.components.extend(components);
+            }
+        }
+        if document.get_element_by_id(&InputId::DiscountRate.to_string()).is_some() {
+            e.discount_rate = fetch::<f64>(document, InputId::DiscountRate, report)?;
+        }
+        if document.get_element_by_id(&InputId::FreeRentStart.to_string()).is_some() {
+            e.free_rent = FreeRent {
+                covers_management_fee: is_checked(document, InputId::FreeRentCoversManagementFee),
+                start: fetch::<i32>(document, InputId::FreeRentStart, report)? - 1,
+                min_stay: fetch::<i32>(document, InputId::FreeRentMinStay, report)?,
+            };
+        }
+        if document.get_element_by_id(&InputId::CurrentRent.to_string()).is_some() {
+            e.current_home = CurrentHome {
+                rent: fetch::<Yen>(document, InputId::CurrentRent, report)?.0,
+                notice_months: fetch::<i32>(document, InputId::CurrentNotice, report)?,
+                move_out: read_date(document, InputId::CurrentMoveOut).flatten(),
+                in_total: is_checked(document, InputId::OverlapInTotal),
+            };
+        }
+        if document.get_element_by_id(&InputId::RenewalHorizon.to_string()).is_some() {
+            e.renewal = Renewal {
+                months_left: fetch::<i32>(document, InputId::RenewalMonthsLeft, report)?,
+                renewal_fee: fetch::<Yen>(document, InputId::RenewalFee, report)?.0,
+                renewed_rent: fetch::<Yen>(document, InputId::RenewedRent, report)?.0,
+                contract_period: fetch::<i32>(document, InputId::RenewalContractPeriod, report)?,
+                horizon: fetch::<i32>(document, InputId::RenewalHorizon, report)?,
+            };
+        }
+        if document.get_element_by_id(&InputId::MovingInTotal.to_string()).is_some() {
+            e.moving.items.clear();
+            for kind in MovingKind::iter() {
+                e.moving.set(kind, fetch::<Yen>(document, kind.input_id(), report)?.0);
+            }
+            e.moving.in_total = is_checked(document, InputId::MovingInTotal);
+        }
+        if document.get_element_by_id(&InputId::RunningPresets.to_string()).is_some() {
+            e.running.items.clear();
+            for kind in RunningKind::iter() {
+                let value = fetch::<Yen>(document, kind.input_id(), report)?.0;
+                e.running.set(kind, value, is_checked(document, kind.included_input_id()));
+            }
+        }
+        if document.get_element_by_id(&InputId::Occupants.to_string()).is_some() {
+            e.area = Area {
+                square_meters: fetch::<f64>(document, InputId::SquareMeters, report)?,
+                jo: fetch::<f64>(document, InputId::Jo, report)?,
+            };
+            e.occupants = fetch::<i32>(document, InputId::Occupants, report)?;
+        }
+        if document.get_element_by_id(&InputId::AnnualIncome.to_string()).is_some() {
+            e.income = Income {
+                annual_gross: fetch::<Yen>(document, InputId::AnnualIncome, report)?.0,
+                monthly_take_home: fetch::<Yen>(document, InputId::TakeHomePay, report)?.0,
+                budget_percent: fetch::<f64>(document, InputId::BudgetPercent, report)?,
+            };
+        }
+        if let Some(paid_items) = read_paid_items(document) {
+            e.subsidy = Subsidy {
+                allowance: fetch::<Yen>(document, InputId::HousingAllowance, report)?.0,
+                rent_percent: fetch::<f64>(document, InputId::EmployerRentPercent, report)?,
+                rent_cap: fetch::<Yen>(document, InputId::EmployerRentCap, report)?.0,
+                paid_items,
+                rent_limit: fetch::<Yen>(document, InputId::SubsidyRentLimit, report)?.0,
+            };
+        }
+        if document.get_element_by_id(&InputId::HomeOfficePercent.to_string()).is_some() {
+            e.home_office = HomeOffice {
+                percent: fetch::<f64>(document, InputId::HomeOfficePercent, report)?,
+                items: read_item_percents(document),
+            };
+        }
+        if document.get_element_by_id(&InputId::PurchaseHorizon.to_string()).is_some() {
+            e.purchase = Purchase {
+                price: fetch::<Yen>(document, InputId::PurchasePrice, report)?.0,
+                down_payment: fetch::<Yen>(document, InputId::DownPayment, report)?.0,
+                mortgage_rate: fetch::<f64>(document, InputId::MortgageRate, report)?,
+                mortgage_years: fetch::<i32>(document, InputId::MortgageYears, report)?,
+                management_fee: fetch::<Yen>(document, InputId::OwnerManagementFee, report)?.0,
+                repair_reserve: fetch::<Yen>(document, InputId::RepairReserve, report)?.0,
+                property_tax: fetch::<Yen>(document, InputId::PropertyTax, report)?.0,
+                closing_costs: fetch::<Yen>(document, InputId::ClosingCosts, report)?.0,
+                resale_value: fetch::<Yen>(document, InputId::ResaleValue, report)?.0,
+                horizon_years: fetch::<i32>(document, InputId::PurchaseHorizon, report)?,
+            };
+        }
+        if let Some(split) = read_split(document) {
+            e.split = split;
+        }
+        if let Some(schedule) = read_schedule(document) {
+            e.schedule = schedule;
+        }
+        Ok(e)
     }
 }
 
+fn is_checked(document: &Document, id: InputId) -> bool {
+    document
+        .get_element_by_id(&id.to_string())
+        .and_then(|e| e.dyn_into::<HtmlInputElement>().ok())
+        .is_some_and(|i| i.checked())
+}
+
+/// `None` if the input is missing, `Some(None)` if it is empty.
+fn read_date(document: &Document, id: InputId) -> Option<Option<Date>> {
+    let input = document
+        .get_element_by_id(&id.to_string())?
+        .dyn_into::<HtmlInputElement>()
+        .ok()?;
+    // the browser leaves the value empty unless it is a valid date
+    Some(Date::try_from(input.value()).ok())
+}
+
 pub struct RentCalculator {}
 
 impl RentCalculator {
     fn total(&self, e: &RentElements) -> i32 {
-        let lease_period = e.lease_period.get(0, 0);
+        let lease_period = e.lease_months();
         let contract_period = e.contract_period.get(0, 0);
 
-        // for simplicity
-        let cget = |lease_period: i32, contract_period: i32| -> Box<dyn Fn(&RentItem) -> i32> {
-            Box::new(move |x| x.get(lease_period, contract_period))
-        };
+        e.cost_items()
+            .into_iter()
+            .map(|kind| e.item_total(kind, lease_period, contract_period))
+            .sum::<i32>()
+            - e.free_rent_discount(lease_period, contract_period)
+            + if e.current_home.in_total { e.overlap_cost() } else { 0 }
+            + e.moving.counted()
+    }
 
-        let f = cget(lease_period, contract_period);
-        let rent_sum = f(&e.rent);
-        let rent = rent_sum / lease_period;
-
-        rent_sum
-            + f(&e.management_fee)
-            + f(&e.guarantee_fee)
-            + f(&e.association_membership_fee)
-            - (rent * f(&e.free_rent_period))
-            + f(&e.key_money)
-            + f(&e.brokerage_fee)
-            + f(&e.guarantee_administrative_fee)
-            + f(&e.insurance_fee)
-            + f(&e.bicycle_space_fee)
-            + f(&e.car_space_fee)
-            + f(&e.key_change_fee)
-            + f(&e.cleaning_fee)
-            + f(&e.contract_update_fee)
-    }
-    pub fn calc(&self, e: RentElements) -> Result<RentResult, JsValue> {
-        let total = self.total(&e);
+    fn terms(&self, e: &RentElements, lease_period: i32, contract_period: i32) -> Vec<TermSummary> {
+        let lengths = term_lengths(lease_period, contract_period);
+        let rents = e.term_values(ItemKind::Rent, lengths.len());
+        let management_fees = e.term_values(ItemKind::ManagementFee, lengths.len());
+        let fee = e.contract_type.extension_fee();
+        let update_fees = fee.map_or(vec![0; lengths.len()], |kind| e.term_values(kind, lengths.len()));
+        let unit = fee.map(|kind| e.item(kind).unit);
+        lengths.iter().enumerate().map(|(t, months)| TermSummary {
+            months: *months,
+            rent: rents[t],
+            management_fee: management_fees[t],
+            renewal_fee: match (t, unit) {
+                (0, _) => 0,
+                (_, Some(UnitId::UpdateRentPercent)) => rents[t] * update_fees[t] / 100,
+                _ => update_fees[t],
+            },
+        }).collect()
+    }
 
-        let lease_period = e.lease_period.get(0, 0);
+    pub fn calc(&self, e: RentElements) -> Result<RentResult, CalcError> {
+        let lease_period = e.lease_months();
         let contract_period = e.contract_period.get(0, 0);
+        if lease_period <= 0 || e.lease_days() <= 0 {
+            return Err(CalcError::InvalidPeriod(ItemKind::LeasePeriod));
+        }
+        if contract_period <= 0 {
+            return Err(CalcError::InvalidPeriod(ItemKind::ContractPeriod));
+        }
+        if e.occupants <= 0 {
+            return Err(CalcError::NoOccupants);
+        }
+        e.renewal.check()?;
 
-        // for simplicity
-        let cget = |lease_period: i32, contract_period: i32| -> Box<dyn Fn(&RentItem) -> i32> {
-            Box::new(move |x| x.get(lease_period, contract_period))
-        };
-        let f = cget(lease_period, contract_period);
+        let total = self.total(&e);
+        let stay = e.stay();
+        let terms = term_lengths(lease_period, contract_period).len();
+        let rents = e.term_values(ItemKind::Rent, terms);
+        let management_fees = e.term_values(ItemKind::ManagementFee, terms);
+        let last = terms - 1;
+
+        let rate = monthly_rate(e.discount_rate);
+        let deposit_cost = deposit_cost(e.deposit.value, lease_period, rate);
+        let present_value = present_value(&cash_flows(&e), rate) + deposit_cost;
+
+        let living_total = total + e.over_stay(e.running.monthly());
 
-        let original_total = f(&e.rent) + f(&e.management_fee);
+        let original_total = e.item_total(ItemKind::Rent, lease_period, contract_period)
+            + e.item_total(ItemKind::ManagementFee, lease_period, contract_period);
+        // the rest of the result stands when the horizon does not reach the renewal
+        let (decision, plan) = if e.renewal.horizon > 0 { (compare(&e).ok(), plan(&e).ok()) } else { (None, None) };
         let result = RentResult {
             lease_period,
             total,
-            monthly: total / lease_period,
+            lease_days: e.lease_days(),
+            counted_in_days: e.counted_in_days(),
+            monthly: e.per_month(total),
+            daily: total / e.lease_days(),
             original_total,
-            original_monthly: original_total / lease_period,
+            original_monthly: e.per_month(original_total),
+            terms: self.terms(&e, lease_period, contract_period),
+            contract_type: e.contract_type,
+            exceeds_term: e.contract_type.extension_fee().is_none() && lease_period > contract_period,
+            move_in: stay.map(|s| s.move_in),
+            move_out: stay.map(|s| s.move_out),
+            first_month: stay.map(|s| s.first_month(rents[0]) + s.first_month(management_fees[0])),
+            last_month: stay.map(|s| s.last_month(rents[last]) + s.last_month(management_fees[last])),
+            move_in_payment: stay.map(|s| {
+                let first = s.first_month(rents[0]) + s.first_month(management_fees[0]);
+                match s.calendar_months() {
+                    1 => first,
+                    // the next month is the move-out month
+                    2 => first + s.last_month(rents[last]) + s.last_month(management_fees[last]),
+                    _ => first + e.next_month_amount(lease_period, contract_period),
+                }
+            }),
+            free_rent_discount: e.free_rent_discount(lease_period, contract_period),
+            free_rent_clawed_back: e.free_rent_clawed_back(lease_period),
+            discount_rate: e.discount_rate,
+            present_value: present_value.round() as i32,
+            level_monthly: level_payment(present_value, lease_period, rate).round() as i32,
+            deposit_cost: deposit_cost.round() as i32,
+            overlap_days: e.current_home.overlap_days(stay.map(|s| s.move_in)),
+            overlap_cost: e.overlap_cost(),
+            overlap_in_total: e.current_home.in_total,
+            notice_by: e.current_home.notice_by(),
+            moving_costs: e.moving.items.clone(),
+            moving_total: e.moving.total(),
+            moving_in_total: e.moving.in_total,
+            running_monthly: e.running.monthly(),
+            living_total,
+            living_monthly: e.per_month(living_total),
+            area: e.area,
+            occupants: e.occupants,
+            per_square_meter: per_unit(e.per_month(total), e.area.square_meters),
+            per_jo: per_unit(e.per_month(total), e.area.jo),
+            per_person: e.per_month(total) / e.occupants,
+            shares: split_costs(&e),
+            affordability: e.income.report(
+                rents[0] + management_fees[0], e.per_month(total), e.per_month(living_total),
+            ),
+            subsidy: subsidize(&e, total, rents[0] + management_fees[0]),
+            home_office: home_office_report(&e),
+            rent_vs_buy: if e.purchase.horizon_years > 0 { Some(rent_vs_buy(&e)?) } else { None },
+            horizon_too_short: e.renewal.horizon > 0 && decision.is_none(),
+            decision,
+            plan,
         };
 
         Ok(result)
@@ -194,12 +799,17 @@ impl RentCalculator {
 
 #[cfg(test)]
 mod tests {
-    use crate::calculator::RentItem;
-    use crate::constants::UnitId;
+    use crate::calculator::{RentCalculator, RentElements, RentItem};
+    use crate::components::Component;
+    use crate::constants::{ContractType, ItemKind, MovingKind, RunningKind, UnitId};
+    use crate::date::Date;
+    use crate::decision::Renewal;
+    use crate::moving::CurrentHome;
+    use crate::schedule::{Change, TermChange};
 
     #[test]
     fn every_contract_update() {
-        let e = RentItem { value: 111, unit: UnitId::EveryContractUpdateYen };
+        let e = RentItem::new(111, UnitId::EveryContractUpdateYen);
         assert_eq!(e.get(1, 1), 0);
         assert_eq!(e.get(10, 11), 0);
         assert_eq!(e.get(11, 11), 0);
@@ -210,7 +820,7 @@ mod tests {
 
     #[test]
     fn one_shot() {
-        let e = RentItem { value: 111, unit: UnitId::OneShotYen };
+        let e = RentItem::new(111, UnitId::OneShotYen);
         assert_eq!(e.get(1, 1), 111);
         assert_eq!(e.get(10, 11), 111);
         assert_eq!(e.get(11, 11), 111);
@@ -221,7 +831,7 @@ mod tests {
 
     #[test]
     fn every_contract() {
-        let e = RentItem { value: 111, unit: UnitId::EveryContractYen };
+        let e = RentItem::new(111, UnitId::EveryContractYen);
         assert_eq!(e.get(1, 1), 111);
         assert_eq!(e.get(10, 11), 111);
         assert_eq!(e.get(11, 11), 111);
@@ -229,4 +839,283 @@ mod tests {
         assert_eq!(e.get(22, 11), 222);
         assert_eq!(e.get(23, 11), 333);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn calc_default() {
+        let r = RentCalculator {}.calc(RentElements::default()).unwrap();
+        assert_eq!(r.lease_period, 24);
+        assert_eq!(r.original_total, 52000 * 24);
+        assert_eq!(r.total, 52000 * 24 + 10000 + 3000 + 50000 * 2 + 5000);
+        assert_eq!(r.monthly, r.total / 24);
+    }
+
+    #[test]
+    fn calc_rejects_zero_lease_period() {
+        let mut e = RentElements::default();
+        e.item_mut(ItemKind::LeasePeriod).value = 0;
+        assert!(RentCalculator {}.calc(e).is_err());
+    }
+
+    #[test]
+    fn calc_with_schedule() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 48;
+        e.contract_update_fee = RentItem::new(100, UnitId::UpdateRentPercent);
+        e.schedule = vec![
+            TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Percent(4.0) },
+            TermChange { renewal: 1, item: ItemKind::InsuranceFee, change: Change::Set(8000) },
+        ];
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.original_total, 50000 * 24 + 52000 * 24 + 2000 * 48);
+        assert_eq!(r.terms.len(), 2);
+        assert_eq!(r.terms[1].rent, 52000);
+        assert_eq!(r.terms[1].renewal_fee, 52000);
+        // bicycle parking is paid for every contract
+        assert_eq!(r.total, r.original_total + 52000 + 10000 + 8000 + 3000 * 2 + 50000 * 2 + 5000);
+    }
+
+    #[test]
+    fn calc_fixed_term() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 48;
+        e.contract_update_fee.value = 50000;
+        e.recontract_fee.value = 20000;
+        let ordinary = RentCalculator {}.calc(e.clone()).unwrap();
+
+        e.contract_type = ContractType::FixedTerm;
+        let fixed = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(fixed.total, ordinary.total - 30000);
+        assert_eq!(fixed.terms[1].renewal_fee, 20000);
+        assert!(!fixed.exceeds_term);
+
+        e.contract_type = ContractType::FixedTermNoRecontract;
+        let no_recontract = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(no_recontract.total, ordinary.total - 50000);
+        assert!(no_recontract.exceeds_term);
+
+        e.lease_period.value = 24;
+        assert!(!RentCalculator {}.calc(e).unwrap().exceeds_term);
+    }
+
+    #[test]
+    fn calc_with_dates() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 1;
+        e.move_in = Date::new(2024, 4, 21);
+        e.move_out = Date::new(2026, 4, 20);
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.lease_period, 24);
+        // each item is rounded to the yen
+        assert_eq!(r.first_month, Some(16667 + 667));
+        assert_eq!(r.last_month, Some(33333 + 1333));
+        assert_eq!(r.move_in_payment, Some(16667 + 667 + 52000));
+        assert_eq!(r.original_total, 52000 * 24);
+
+        // moving out one day later starts a new month, and a renewal
+        e.move_out = Date::new(2026, 4, 21);
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.lease_period, 25);
+        assert_eq!(r.original_total, (16667 + 667) + 52000 * 23 + (35000 + 1400));
+        assert_eq!(r.terms.len(), 2);
+    }
+
+    #[test]
+    fn move_in_payment() {
+        let mut e = RentElements {
+            contract_period: RentItem::new(1, UnitId::Month),
+            move_in: Date::new(2024, 4, 21),
+            ..Default::default()
+        };
+        // the month after move-in is in the second term, at the new rent
+        e.schedule = vec![TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Set(60000) }];
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.move_in_payment, Some(16667 + 667 + 60000 + 2000));
+
+        e.free_rent_period.value = 1;
+        e.free_rent.start = 1;
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.move_in_payment, Some(16667 + 667 + 2000));
+
+        e.free_rent.covers_management_fee = true;
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.move_in_payment, Some(16667 + 667));
+    }
+
+    #[test]
+    fn short_horizon_keeps_result() {
+        let e = RentElements {
+            renewal: Renewal { months_left: 12, contract_period: 24, horizon: 6, ..Default::default() },
+            ..Default::default()
+        };
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.total, RentCalculator {}.calc(RentElements { renewal: Renewal::default(), ..e }).unwrap().total);
+        assert!(r.horizon_too_short);
+        assert_eq!(r.decision, None);
+        assert_eq!(r.plan, None);
+    }
+
+    #[test]
+    fn per_day_units() {
+        let e = RentItem::new(700, UnitId::DailyYen);
+        assert_eq!(e.get_days(10, 24), 7000);
+        assert_eq!(e.get(2, 24), 42000);
+        let e = RentItem::new(7000, UnitId::WeeklyYen);
+        assert_eq!(e.get_days(7, 24), 7000);
+        assert_eq!(e.get_days(8, 24), 14000);
+        assert_eq!(e.get_days(100, 1), 15 * 7000);
+    }
+
+    #[test]
+    fn calc_in_days() {
+        let mut e = RentElements {
+            rent: RentItem::new(4000, UnitId::DailyYen),
+            lease_period: RentItem::new(45, UnitId::Day),
+            ..Default::default()
+        };
+        e.management_fee.value = 3000;
+        e.key_money.value = 0;
+        e.brokerage_fee.value = 0;
+        e.insurance_fee.value = 0;
+        e.bicycle_space_fee.value = 0;
+        e.key_change_fee.value = 0;
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.lease_period, 2);
+        assert_eq!(r.lease_days, 45);
+        assert!(r.counted_in_days);
+        assert_eq!(r.total, 4000 * 45 + 3000 + 1500);
+        assert_eq!(r.daily, 4100);
+        assert_eq!(r.monthly, 4100 * 30);
+    }
+
+    #[test]
+    fn recurring_units() {
+        let e = RentItem::new(10000, UnitId::YearlyYen);
+        assert_eq!(e.get(12, 24), 10000);
+        assert_eq!(e.get(13, 24), 20000);
+        assert_eq!(e.get(36, 24), 30000);
+        // a 2-year insurance on a 3-year contract
+        let e = RentItem { interval: 24, ..RentItem::new(15000, UnitId::EveryNMonthsYen) };
+        assert_eq!(e.get(36, 36), 30000);
+        assert_eq!(e.get(60, 36), 45000);
+        let e = RentItem { interval: 3, ..RentItem::new(1000, UnitId::EveryNMonthsYen) };
+        assert_eq!(e.get(10, 24), 4000);
+        assert_eq!(e.get_days(45, 24), 1000);
+    }
+
+    #[test]
+    fn recurring_units_follow_schedule() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 48;
+        e.insurance_fee = RentItem::new(10000, UnitId::YearlyYen);
+        e.schedule = vec![TermChange { renewal: 1, item: ItemKind::InsuranceFee, change: Change::Set(12000) }];
+        assert_eq!(e.item_total(ItemKind::InsuranceFee, 48, 24), 10000 * 2 + 12000 * 2);
+    }
+
+    #[test]
+    fn calc_with_components() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 30;
+        e.guarantee_fee.value = 26000;
+        e.components = vec![
+            Component { item: ItemKind::GuaranteeFee, charge: RentItem::new(10000, UnitId::YearlyYen) },
+            Component { item: ItemKind::SupportFee, charge: RentItem::new(500, UnitId::MonthlyYen) },
+        ];
+        assert_eq!(e.item_total(ItemKind::GuaranteeFee, 30, 24), 26000 + 10000 * 3);
+        assert_eq!(e.item_total(ItemKind::SupportFee, 30, 24), 500 * 30);
+        let with = RentCalculator {}.calc(e.clone()).unwrap();
+        e.components.clear();
+        let without = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(with.total, without.total + 30000 + 15000);
+    }
+
+    #[test]
+    fn free_rent() {
+        let mut e = RentElements::default();
+        e.free_rent_period.value = 2;
+        let base = RentCalculator {}.calc(RentElements::default()).unwrap();
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.free_rent_discount, 100000);
+        assert_eq!(r.total, base.total - 100000);
+
+        e.free_rent.covers_management_fee = true;
+        assert_eq!(e.free_rent_discount(24, 24), 104000);
+
+        // only the months within the lease count
+        e.free_rent.start = 23;
+        assert_eq!(e.free_rent_discount(24, 24), 52000);
+
+        // the rent in force at that time
+        e.schedule = vec![TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Set(60000) }];
+        assert_eq!(e.free_rent_discount(48, 24), 52000 + 62000);
+
+        e.free_rent.min_stay = 36;
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert!(r.free_rent_clawed_back);
+        assert_eq!(r.free_rent_discount, 0);
+    }
+
+    #[test]
+    fn calc_discounted() {
+        let mut e = RentElements::default();
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.present_value, r.total);
+        assert_eq!(r.deposit_cost, 0);
+        // monthly is rounded down
+        assert!((r.level_monthly - r.monthly).abs() <= 1);
+
+        e.discount_rate = 5.0;
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert!(r.deposit_cost > 0);
+        assert!(r.present_value < r.total);
+        // paying the initial fees up front costs more than spreading them
+        assert!(r.level_monthly > r.monthly);
+    }
+
+    #[test]
+    fn calc_with_current_home() {
+        let home = CurrentHome { rent: 90000, notice_months: 1, ..Default::default() };
+        let e = RentElements { current_home: home, move_in: Date::new(2024, 4, 21), ..Default::default() };
+        let base = RentCalculator {}.calc(RentElements { current_home: CurrentHome::default(), ..e.clone() }).unwrap();
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.overlap_days, 30);
+        assert_eq!(r.overlap_cost, 90000);
+        assert_eq!(r.total, base.total);
+
+        let home = CurrentHome { in_total: true, move_out: Date::new(2024, 4, 30), ..home };
+        let r = RentCalculator {}.calc(RentElements { current_home: home, ..e }).unwrap();
+        assert_eq!(r.overlap_cost, 30000);
+        assert_eq!(r.total, base.total + 30000);
+        assert_eq!(r.notice_by, Date::new(2024, 3, 31));
+    }
+
+    #[test]
+    fn calc_with_moving_costs() {
+        let base = RentCalculator {}.calc(RentElements::default()).unwrap();
+        let mut e = RentElements::default();
+        e.moving.set(MovingKind::Movers, 48000);
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.moving_total, 48000);
+        assert_eq!(r.total, base.total + 48000);
+        assert_eq!(r.monthly, base.monthly + 2000);
+
+        e.moving.in_total = false;
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.moving_total, 48000);
+        assert_eq!(r.total, base.total);
+    }
+
+    #[test]
+    fn calc_with_running_costs() {
+        let mut e = RentElements::default();
+        e.running.set(RunningKind::Electricity, 6000, false);
+        e.running.set(RunningKind::Internet, 5000, true);
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        assert_eq!(r.running_monthly, 6000);
+        assert_eq!(r.living_total, r.total + 6000 * 24);
+        assert_eq!(r.living_monthly, r.monthly + 6000);
+
+        e.lease_period = RentItem::new(45, UnitId::Day);
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert_eq!(r.living_total, r.total + 9000);
+    }
+}
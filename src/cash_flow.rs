@@ -0,0 +1,171 @@
+use crate::calculator::{DAYS_PER_MONTH, RentElements, RentItem};
+use crate::constants::{ItemKind, UnitId};
+use crate::date::prorate;
+use crate::schedule::term_lengths;
+
+/// Payments at the start of each month of the lease, and at move-out as the last entry.
+/// Adds up to `RentCalculator`'s total.
+pub fn cash_flows(e: &RentElements) -> Vec<i64> {
+    let mut flows = vec![0; e.lease_months() as usize + 1];
+    for kind in e.cost_items() {
+        for (flow, item) in flows.iter_mut().zip(item_flows(e, kind)) {
+            *flow += item;
+        }
+    }
+    flows[0] += extra_costs(e) as i64;
+    flows
+}
+
+/// Costs in the total that are not paid to the landlord, all paid when moving in.
+pub fn extra_costs(e: &RentElements) -> i32 {
+    let overlap = if e.current_home.in_total { e.overlap_cost() } else { 0 };
+    overlap + e.moving.counted()
+}
+
+/// Same as `cash_flows` for `kind` and its components alone, free months included.
+pub fn item_flows(e: &RentElements, kind: ItemKind) -> Vec<i64> {
+    let lease_period = e.lease_months();
+    let contract_period = e.contract_period.value;
+    let terms = term_lengths(lease_period, contract_period);
+    let mut flows = vec![0; lease_period as usize + 1];
+
+    let values = e.term_values(kind, terms.len());
+    let mut charges = vec![(*e.item(kind), values)];
+    charges.extend(
+        e.components
+            .iter()
+            .filter(|c| c.item == kind)
+            .map(|c| (c.charge, vec![c.charge.value; terms.len()])),
+    );
+    for (charge, values) in charges {
+        let rents = e.term_values(ItemKind::Rent, terms.len());
+        let added = add_charge(&mut flows, kind, &charge, &terms, &values, &rents);
+        // prorated days are settled in the last month
+        let rest = e.charge_total(&charge, &terms, &values, &rents) as i64 - added;
+        flows[lease_period as usize - 1] += rest;
+    }
+
+    let free = kind == ItemKind::Rent || (kind == ItemKind::ManagementFee && e.free_rent.covers_management_fee);
+    if free && !e.free_rent_clawed_back(lease_period) {
+        let start = e.free_rent.start.max(0);
+        for month in (start..start + e.free_rent_period.value.max(0)).filter(|m| *m < lease_period) {
+            flows[month as usize] -= e.month_amount(kind, month, lease_period, contract_period) as i64;
+        }
+    }
+    flows
+}
+
+/// Adds the payments of `charge` to `flows`, returning their sum.
+fn add_charge(flows: &mut [i64], kind: ItemKind, charge: &RentItem,
+              terms: &[i32], values: &[i32], rents: &[i32]) -> i64 {
+    let lease_period = flows.len() - 1;
+    let starts: Vec<usize> = terms
+        .iter()
+        .scan(0, |start, months| {
+            let s = *start;
+            *start += *months as usize;
+            Some(s)
+        })
+        .collect();
+    // value in force at the given month
+    let term_of = |month: usize| starts.iter().rposition(|s| *s <= month).unwrap_or(0);
+
+    let mut payments: Vec<(usize, i64)> = vec![];
+    match charge.unit {
+        UnitId::MonthlyYen | UnitId::DailyYen | UnitId::WeeklyYen => {
+            for month in 0..lease_period {
+                let v = values[term_of(month)];
+                let amount = match charge.unit {
+                    UnitId::DailyYen => v * DAYS_PER_MONTH,
+                    UnitId::WeeklyYen => prorate(v, DAYS_PER_MONTH, 7),
+                    _ => v,
+                };
+                payments.push((month, amount as i64));
+            }
+        }
+        // cleaning is paid when moving out
+        UnitId::OneShotYen if kind == ItemKind::CleaningFee => payments.push((lease_period, values[0] as i64)),
+        UnitId::OneShotYen => payments.push((0, values[0] as i64)),
+        UnitId::EveryContractYen => {
+            payments.extend(starts.iter().zip(values).map(|(s, v)| (*s, *v as i64)));
+        }
+        UnitId::EveryContractUpdateYen => {
+            payments.extend(starts.iter().zip(values).skip(1).map(|(s, v)| (*s, *v as i64)));
+        }
+        UnitId::UpdateRentPercent => {
+            let renewals = starts.iter().zip(values.iter().zip(rents)).skip(1);
+            payments.extend(renewals.map(|(s, (v, r))| (*s, (r * v / 100) as i64)));
+        }
+        UnitId::YearlyYen | UnitId::EveryNMonthsYen => {
+            let period = if charge.unit == UnitId::YearlyYen { 12 } else { charge.interval.max(1) as usize };
+            payments.extend((0..lease_period).step_by(period).map(|m| (m, values[term_of(m)] as i64)));
+        }
+        UnitId::Month | UnitId::Day => {}
+    }
+
+    payments.iter().map(|(month, amount)| {
+        flows[*month] += amount;
+        amount
+    }).sum()
+}
+
+/// Monthly rate equivalent to `annual_percent` a year.
+pub fn monthly_rate(annual_percent: f64) -> f64 {
+    (1.0 + annual_percent / 100.0).powf(1.0 / 12.0) - 1.0
+}
+
+/// Value today of `flows`, the `m`-th one being paid `m` months from now.
+pub fn present_value(flows: &[i64], rate: f64) -> f64 {
+    flows.iter().enumerate().map(|(m, f)| *f as f64 / (1.0 + rate).powi(m as i32)).sum()
+}
+
+/// Level payment at the start of each of `months` months worth `pv` today.
+pub fn level_payment(pv: f64, months: i32, rate: f64) -> f64 {
+    if rate == 0.0 {
+        return pv / months as f64;
+    }
+    pv * rate / ((1.0 - (1.0 + rate).powi(-months)) * (1.0 + rate))
+}
+
+/// Value today of the returns lost by leaving `deposit` until it is paid back after `months` months.
+pub fn deposit_cost(deposit: i32, months: i32, rate: f64) -> f64 {
+    deposit as f64 * (1.0 - 1.0 / (1.0 + rate).powi(months))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentCalculator, RentElements};
+    use crate::cash_flow::{cash_flows, deposit_cost, level_payment, monthly_rate, present_value};
+
+    #[test]
+    fn flows_add_up_to_total() {
+        let mut e = RentElements::default();
+        e.lease_period.value = 30;
+        e.contract_update_fee.value = 50000;
+        e.free_rent_period.value = 1;
+        e.cleaning_fee.value = 30000;
+        let flows = cash_flows(&e);
+        assert_eq!(flows.len(), 31);
+        assert_eq!(flows.iter().sum::<i64>(), RentCalculator {}.calc(e.clone()).unwrap().total as i64);
+        // the first month is free, but the initial fees are due
+        assert_eq!(flows[0], 2000 + 50000 * 2 + 10000 + 3000 + 5000);
+        assert_eq!(flows[24], 52000 + 50000 + 10000 + 3000);
+        assert_eq!(flows[30], 30000);
+
+        e.move_in = crate::date::Date::new(2024, 4, 21);
+        let flows = cash_flows(&e);
+        assert_eq!(flows.iter().sum::<i64>(), RentCalculator {}.calc(e).unwrap().total as i64);
+    }
+
+    #[test]
+    fn discounting() {
+        let r = monthly_rate(12.0);
+        assert!(((1.0 + r).powi(12) - 1.12).abs() < 1e-9);
+        assert_eq!(present_value(&[100, 100], 0.0), 200.0);
+        assert!((present_value(&[0, 112], 0.12) - 100.0).abs() < 1e-9);
+        assert!((level_payment(present_value(&[100, 100, 100], r), 3, r) - 100.0).abs() < 1e-9);
+        assert_eq!(level_payment(300.0, 3, 0.0), 100.0);
+        assert_eq!(deposit_cost(100000, 24, 0.0), 0.0);
+        assert!(deposit_cost(100000, 24, r) > 0.0);
+    }
+}
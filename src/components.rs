@@ -0,0 +1,53 @@
+use std::convert::TryInto;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, HtmlInputElement, HtmlSelectElement};
+
+use crate::calculator::RentItem;
+use crate::constants::{ItemKind, UnitId};
+use crate::yen::parse_yen;
+
+/// A charge of `item` on top of its main value, e.g. the yearly part of a guarantee fee
+/// paid 50% up front plus ¥10,000 a year.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Component {
+    pub item: ItemKind,
+    pub charge: RentItem,
+}
+
+/// Id of the rows rendered by `form_items::RentItem` for the components of `kind`.
+pub fn components_id(kind: ItemKind) -> String {
+    format!("{}_components", kind.input_id())
+}
+
+/// Reads the component rows of `kind`, or `None` if the item has no such rows on the form.
+/// Rows with an empty or unreadable value are skipped.
+pub fn read_components(document: &Document, kind: ItemKind) -> Option<Vec<Component>> {
+    let container = document.get_element_by_id(&components_id(kind))?;
+    let mut components = vec![];
+    let mut row = container.first_element_child();
+    while let Some(r) = row {
+        components.extend(read_row(&r, kind));
+        row = r.next_element_sibling();
+    }
+    Some(components)
+}
+
+fn read_row(row: &Element, kind: ItemKind) -> Option<Component> {
+    let field = |name: &str| -> Option<String> {
+        let e = row.query_selector(&format!("[name={}]", name)).ok()??;
+        e.dyn_ref::<HtmlInputElement>().map(|i| i.value())
+            .or_else(|| e.dyn_ref::<HtmlSelectElement>().map(|s| s.value()))
+    };
+    let unit: UnitId = field("unit")?.parse::<i32>().ok()?.try_into().ok()?;
+    if !kind.units().contains(&unit) {
+        return None;
+    }
+    let interval = field("interval")
+        .and_then(|i| i.parse::<i32>().ok())
+        .filter(|i| *i >= 1)
+        .unwrap_or(RentItem::new(0, unit).interval);
+    let charge = RentItem { interval, ..RentItem::new(parse_yen(&field("value")?)?, unit) };
+    Some(Component { item: kind, charge })
+}
@@ -1,5 +1,6 @@
 use strum_macros::{Display, EnumIter};
 use wasm_bindgen::JsValue;
+use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use strum::IntoEnumIterator;
@@ -11,6 +12,8 @@ pub enum InputId {
     RentUnit,
     ManagementFee,
     ManagementFeeUnit,
+    Deposit,
+    DepositUnit,
     FreeRentPeriod,
     FreeRentPeriodUnit,
     SupportFee,
@@ -37,38 +40,112 @@ pub enum InputId {
     CleaningFeeUnit,
     ContractUpdateFee,
     ContractUpdateFeeUnit,
+    RecontractFee,
+    RecontractFeeUnit,
+
+    GuaranteeFeeInterval,
+    SupportFeeInterval,
+    AssociationMembershipFeeInterval,
+    InsuranceFeeInterval,
+    BicycleSpaceFeeInterval,
+    CarSpaceFeeInterval,
+
+    FreeRentCoversManagementFee,
+    FreeRentStart,
+    FreeRentMinStay,
+    DiscountRate,
+    CurrentRent,
+    CurrentNotice,
+    CurrentMoveOut,
+    OverlapInTotal,
+    RenewalMonthsLeft,
+    RenewalFee,
+    RenewedRent,
+    RenewalContractPeriod,
+    RenewalHorizon,
+    Movers,
+    Curtains,
+    Lighting,
+    AirConditioner,
+    InternetSetup,
+    Disposal,
+    OtherMovingCost,
+    MovingInTotal,
+    MovingPresets,
+    Electricity,
+    ElectricityIncluded,
+    Gas,
+    GasIncluded,
+    Water,
+    WaterIncluded,
+    Internet,
+    InternetIncluded,
+    OtherRunningCost,
+    OtherRunningCostIncluded,
+    RunningPresets,
+    SquareMeters,
+    Jo,
+    Occupants,
+    SplitPeople,
+    SplitRows,
+    AnnualIncome,
+    TakeHomePay,
+    BudgetPercent,
+    HousingAllowance,
+    EmployerRentPercent,
+    EmployerRentCap,
+    SubsidyRentLimit,
+    EmployerPaidItems,
+    HomeOfficePercent,
+    PurchasePrice,
+    DownPayment,
+    MortgageRate,
+    MortgageYears,
+    OwnerManagementFee,
+    RepairReserve,
+    PropertyTax,
+    ClosingCosts,
+    ResaleValue,
+    PurchaseHorizon,
+    ContractType,
+    MoveIn,
+    MoveOut,
 
     ContractPeriod,
     ContractPeriodUnit,
     LeasePeriod,
     LeasePeriodUnit,
 
+    LangSelect,
+    ListingTextArea,
+    ListingReport,
+    LiveSwitch,
     CalcResultTitle,
     CalcResultBody,
     JsonExportTextArea,
     JsonImportTextArea,
+    ScheduleRows,
 }
 
 
-#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone)]
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum UnitId {
     MonthlyYen = 0,
     OneShotYen = 1,
     EveryContractYen = 2,
     EveryContractUpdateYen = 3,
     Month = 4,
-}
-
-impl UnitId {
-    pub fn to_string(&self) -> &str {
-        match self {
-            UnitId::MonthlyYen => "円 / 月",
-            UnitId::OneShotYen => "円（初回のみ）",
-            UnitId::EveryContractYen => "円 / 契約",
-            UnitId::EveryContractUpdateYen => "円 / 契約更新",
-            UnitId::Month => "か月",
-        }
-    }
+    /// Percentage of the rent in force after the renewal.
+    UpdateRentPercent = 5,
+    DailyYen = 6,
+    /// Charged for every started week.
+    WeeklyYen = 7,
+    Day = 8,
+    /// Charged for every started year of the lease.
+    YearlyYen = 9,
+    /// Charged for every started `RentItem::interval` months of the lease.
+    EveryNMonthsYen = 10,
 }
 
 impl TryFrom<i32> for UnitId {
@@ -80,4 +157,298 @@ impl TryFrom<i32> for UnitId {
         first_one.and_then(|e| if mt.next().is_some() { None } else { Some(e) })
             .ok_or_else(|| JsValue::from_str("Invalid integer for UnitId"))
     }
-}
\ No newline at end of file
+}
+
+/// Cost items of a scenario, in the order they appear on the form.
+/// The numbers are part of the JS API, so new items take the next free number wherever they are listed.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ItemKind {
+    Rent = 0,
+    ManagementFee = 1,
+    Deposit = 18,
+    KeyMoney = 2,
+    BrokerageFee = 3,
+    FreeRentPeriod = 4,
+    GuaranteeFee = 5,
+    SupportFee = 6,
+    AssociationMembershipFee = 7,
+    GuaranteeAdministrativeFee = 8,
+    InsuranceFee = 9,
+    BicycleSpaceFee = 10,
+    CarSpaceFee = 11,
+    KeyChangeFee = 12,
+    CleaningFee = 13,
+    ContractUpdateFee = 14,
+    RecontractFee = 15,
+    ContractPeriod = 16,
+    LeasePeriod = 17,
+}
+
+impl ItemKind {
+    pub fn input_id(self) -> InputId {
+        match self {
+            ItemKind::Rent => InputId::Rent,
+            ItemKind::ManagementFee => InputId::ManagementFee,
+            ItemKind::Deposit => InputId::Deposit,
+            ItemKind::KeyMoney => InputId::KeyMoney,
+            ItemKind::BrokerageFee => InputId::BrokerageFee,
+            ItemKind::FreeRentPeriod => InputId::FreeRentPeriod,
+            ItemKind::GuaranteeFee => InputId::GuaranteeFee,
+            ItemKind::SupportFee => InputId::SupportFee,
+            ItemKind::AssociationMembershipFee => InputId::AssociationMembershipFee,
+            ItemKind::GuaranteeAdministrativeFee => InputId::GuaranteeAdministrativeFee,
+            ItemKind::InsuranceFee => InputId::InsuranceFee,
+            ItemKind::BicycleSpaceFee => InputId::BicycleSpaceFee,
+            ItemKind::CarSpaceFee => InputId::CarSpaceFee,
+            ItemKind::KeyChangeFee => InputId::KeyChangeFee,
+            ItemKind::CleaningFee => InputId::CleaningFee,
+            ItemKind::ContractUpdateFee => InputId::ContractUpdateFee,
+            ItemKind::RecontractFee => InputId::RecontractFee,
+            ItemKind::ContractPeriod => InputId::ContractPeriod,
+            ItemKind::LeasePeriod => InputId::LeasePeriod,
+        }
+    }
+
+    pub fn unit_input_id(self) -> InputId {
+        match self {
+            ItemKind::Rent => InputId::RentUnit,
+            ItemKind::ManagementFee => InputId::ManagementFeeUnit,
+            ItemKind::Deposit => InputId::DepositUnit,
+            ItemKind::KeyMoney => InputId::KeyMoneyUnit,
+            ItemKind::BrokerageFee => InputId::BrokerageFeeUnit,
+            ItemKind::FreeRentPeriod => InputId::FreeRentPeriodUnit,
+            ItemKind::GuaranteeFee => InputId::GuaranteeFeeUnit,
+            ItemKind::SupportFee => InputId::SupportFeeUnit,
+            ItemKind::AssociationMembershipFee => InputId::AssociationMembershipFeeUnit,
+            ItemKind::GuaranteeAdministrativeFee => InputId::GuaranteeAdministrativeFeeUnit,
+            ItemKind::InsuranceFee => InputId::InsuranceFeeUnit,
+            ItemKind::BicycleSpaceFee => InputId::BicycleSpaceFeeUnit,
+            ItemKind::CarSpaceFee => InputId::CarSpaceFeeUnit,
+            ItemKind::KeyChangeFee => InputId::KeyChangeFeeUnit,
+            ItemKind::CleaningFee => InputId::CleaningFeeUnit,
+            ItemKind::ContractUpdateFee => InputId::ContractUpdateFeeUnit,
+            ItemKind::RecontractFee => InputId::RecontractFeeUnit,
+            ItemKind::ContractPeriod => InputId::ContractPeriodUnit,
+            ItemKind::LeasePeriod => InputId::LeasePeriodUnit,
+        }
+    }
+
+    /// Input for the N of `UnitId::EveryNMonthsYen`, for items that can recur on their own cycle.
+    pub fn interval_input_id(self) -> Option<InputId> {
+        match self {
+            ItemKind::GuaranteeFee => Some(InputId::GuaranteeFeeInterval),
+            ItemKind::SupportFee => Some(InputId::SupportFeeInterval),
+            ItemKind::AssociationMembershipFee => Some(InputId::AssociationMembershipFeeInterval),
+            ItemKind::InsuranceFee => Some(InputId::InsuranceFeeInterval),
+            ItemKind::BicycleSpaceFee => Some(InputId::BicycleSpaceFeeInterval),
+            ItemKind::CarSpaceFee => Some(InputId::CarSpaceFeeInterval),
+            _ => None,
+        }
+    }
+
+    /// Whether the item is an amount of money rather than a number of months.
+    pub fn is_yen(self) -> bool {
+        self.units()[0] != UnitId::Month
+    }
+
+    /// Units selectable for the item. The first one is the default.
+    pub fn units(self) -> Vec<UnitId> {
+        let mut units = self.base_units();
+        if self.interval_input_id().is_some() {
+            units.extend(&[UnitId::YearlyYen, UnitId::EveryNMonthsYen]);
+        }
+        units
+    }
+
+    fn base_units(self) -> Vec<UnitId> {
+        match self {
+            ItemKind::Rent => vec![UnitId::MonthlyYen, UnitId::DailyYen, UnitId::WeeklyYen],
+            ItemKind::ManagementFee => vec![UnitId::MonthlyYen, UnitId::DailyYen, UnitId::WeeklyYen],
+            ItemKind::Deposit => vec![UnitId::OneShotYen],
+            ItemKind::KeyMoney => vec![UnitId::OneShotYen],
+            ItemKind::BrokerageFee => vec![UnitId::OneShotYen],
+            ItemKind::FreeRentPeriod => vec![UnitId::Month],
+            ItemKind::GuaranteeFee => vec![UnitId::OneShotYen, UnitId::MonthlyYen, UnitId::EveryContractYen],
+            ItemKind::SupportFee => vec![UnitId::MonthlyYen, UnitId::EveryContractYen, UnitId::OneShotYen],
+            ItemKind::AssociationMembershipFee => vec![UnitId::MonthlyYen, UnitId::EveryContractYen, UnitId::OneShotYen],
+            ItemKind::GuaranteeAdministrativeFee => vec![UnitId::OneShotYen],
+            ItemKind::InsuranceFee => vec![UnitId::EveryContractYen, UnitId::OneShotYen],
+            ItemKind::BicycleSpaceFee => vec![UnitId::EveryContractYen, UnitId::MonthlyYen, UnitId::OneShotYen],
+            ItemKind::CarSpaceFee => vec![UnitId::EveryContractYen, UnitId::MonthlyYen, UnitId::OneShotYen],
+            ItemKind::KeyChangeFee => vec![UnitId::OneShotYen],
+            ItemKind::CleaningFee => vec![UnitId::OneShotYen],
+            ItemKind::ContractUpdateFee => vec![UnitId::EveryContractUpdateYen, UnitId::UpdateRentPercent],
+            ItemKind::RecontractFee => vec![UnitId::EveryContractUpdateYen, UnitId::UpdateRentPercent],
+            ItemKind::ContractPeriod => vec![UnitId::Month],
+            ItemKind::LeasePeriod => vec![UnitId::Month, UnitId::Day],
+        }
+    }
+}
+
+impl TryFrom<i32> for ItemKind {
+    type Error = JsValue;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        ItemKind::iter()
+            .find(|x| *x as i32 == value)
+            .ok_or_else(|| JsValue::from_str("Invalid integer for ItemKind"))
+    }
+}
+
+/// How the lease goes on after the contract period.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ContractType {
+    /// 普通借家: renewed with `ContractUpdateFee`.
+    #[default]
+    Ordinary,
+    /// 定期借家: ends at the term, and continues only by a re-contract with `RecontractFee`.
+    FixedTerm,
+    /// 定期借家 with no re-contract: the stay cannot go beyond the term.
+    FixedTermNoRecontract,
+}
+
+impl ContractType {
+    /// Item charged each time the contract is extended, if it can be.
+    pub fn extension_fee(self) -> Option<ItemKind> {
+        match self {
+            ContractType::Ordinary => Some(ItemKind::ContractUpdateFee),
+            ContractType::FixedTerm => Some(ItemKind::RecontractFee),
+            ContractType::FixedTermNoRecontract => None,
+        }
+    }
+}
+
+impl TryFrom<i32> for ContractType {
+    type Error = JsValue;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        ContractType::iter()
+            .find(|x| *x as i32 == value)
+            .ok_or_else(|| JsValue::from_str("Invalid integer for ContractType"))
+    }
+}
+
+/// One-off costs of moving in, not charged by the landlord.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MovingKind {
+    Movers,
+    Curtains,
+    /// Ceiling lights, often not provided.
+    Lighting,
+    AirConditioner,
+    InternetSetup,
+    /// Disposing of the furniture left behind.
+    Disposal,
+    Other,
+}
+
+impl MovingKind {
+    pub fn input_id(self) -> InputId {
+        match self {
+            MovingKind::Movers => InputId::Movers,
+            MovingKind::Curtains => InputId::Curtains,
+            MovingKind::Lighting => InputId::Lighting,
+            MovingKind::AirConditioner => InputId::AirConditioner,
+            MovingKind::InternetSetup => InputId::InternetSetup,
+            MovingKind::Disposal => InputId::Disposal,
+            MovingKind::Other => InputId::OtherMovingCost,
+        }
+    }
+
+    /// Typical amount for a single person's move, filled in by the presets button.
+    pub fn preset(self) -> i32 {
+        match self {
+            MovingKind::Movers => 50000,
+            MovingKind::Curtains => 15000,
+            MovingKind::Lighting => 10000,
+            MovingKind::AirConditioner => 15000,
+            MovingKind::InternetSetup => 20000,
+            MovingKind::Disposal => 10000,
+            MovingKind::Other => 0,
+        }
+    }
+}
+
+/// Monthly costs of living in the property besides what is paid to the landlord.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Display, EnumIter, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RunningKind {
+    Electricity,
+    /// City gas or propane, which costs about twice as much. 0 for all-electric.
+    Gas,
+    /// Often a flat rate (水道定額) in small apartments.
+    Water,
+    Internet,
+    Other,
+}
+
+impl RunningKind {
+    pub fn input_id(self) -> InputId {
+        match self {
+            RunningKind::Electricity => InputId::Electricity,
+            RunningKind::Gas => InputId::Gas,
+            RunningKind::Water => InputId::Water,
+            RunningKind::Internet => InputId::Internet,
+            RunningKind::Other => InputId::OtherRunningCost,
+        }
+    }
+
+    /// Checkbox telling the cost is included in the rent.
+    pub fn included_input_id(self) -> InputId {
+        match self {
+            RunningKind::Electricity => InputId::ElectricityIncluded,
+            RunningKind::Gas => InputId::GasIncluded,
+            RunningKind::Water => InputId::WaterIncluded,
+            RunningKind::Internet => InputId::InternetIncluded,
+            RunningKind::Other => InputId::OtherRunningCostIncluded,
+        }
+    }
+
+    /// Typical monthly amount for a single person with city gas, filled in by the presets button.
+    pub fn preset(self) -> i32 {
+        match self {
+            RunningKind::Electricity => 6000,
+            RunningKind::Gas => 4000,
+            RunningKind::Water => 2500,
+            RunningKind::Internet => 5000,
+            RunningKind::Other => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::constants::ItemKind;
+
+    #[test]
+    fn item_numbers_are_stable() {
+        let numbers: Vec<(ItemKind, i32)> = vec![
+            (ItemKind::Rent, 0),
+            (ItemKind::ManagementFee, 1),
+            (ItemKind::KeyMoney, 2),
+            (ItemKind::BrokerageFee, 3),
+            (ItemKind::FreeRentPeriod, 4),
+            (ItemKind::GuaranteeFee, 5),
+            (ItemKind::SupportFee, 6),
+            (ItemKind::AssociationMembershipFee, 7),
+            (ItemKind::GuaranteeAdministrativeFee, 8),
+            (ItemKind::InsuranceFee, 9),
+            (ItemKind::BicycleSpaceFee, 10),
+            (ItemKind::CarSpaceFee, 11),
+            (ItemKind::KeyChangeFee, 12),
+            (ItemKind::CleaningFee, 13),
+            (ItemKind::ContractUpdateFee, 14),
+            (ItemKind::RecontractFee, 15),
+            (ItemKind::ContractPeriod, 16),
+            (ItemKind::LeasePeriod, 17),
+            (ItemKind::Deposit, 18),
+        ];
+        for (kind, number) in numbers {
+            assert_eq!(kind as i32, number);
+            assert_eq!(ItemKind::try_from(number).ok(), Some(kind));
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::InputError;
+
+/// A calendar date, written as `2024-04-15` like `<input type="date">` does.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date {
+    year: i32,
+    month: i32,
+    day: i32,
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    pub fn new(year: i32, month: i32, day: i32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    pub fn days_in_month(self) -> i32 {
+        days_in_month(self.year, self.month)
+    }
+
+    /// The same day `n` months later (or earlier), moved back to the end of shorter months.
+    pub fn add_months(self, n: i32) -> Date {
+        let m = self.year * 12 + self.month - 1 + n;
+        let (year, month) = (m.div_euclid(12), m.rem_euclid(12) + 1);
+        Date { year, month, day: self.day.min(days_in_month(year, month)) }
+    }
+
+    pub fn next_day(self) -> Date {
+        self.add_days(1)
+    }
+
+    pub fn prev_day(self) -> Date {
+        self.add_days(-1)
+    }
+
+    /// Days since 1970-01-01.
+    fn ordinal(self) -> i32 {
+        let y = if self.month <= 2 { self.year - 1 } else { self.year };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * ((self.month + 9) % 12) + 2) / 5 + self.day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    fn from_ordinal(days: i32) -> Date {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year, month, day }
+    }
+
+    pub fn add_days(self, n: i32) -> Date {
+        Date::from_ordinal(self.ordinal() + n)
+    }
+
+    /// Days from `earlier` to `self`, negative if `earlier` is later.
+    pub fn days_since(self, earlier: Date) -> i32 {
+        self.ordinal() - earlier.ordinal()
+    }
+
+    /// Number of calendar months from the month of `self` to the month of `other`.
+    fn month_diff(self, other: Date) -> i32 {
+        (other.year - self.year) * 12 + other.month - self.month
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl From<Date> for String {
+    fn from(val: Date) -> Self {
+        val.to_string()
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = InputError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let parts: Vec<i32> = value
+            .trim()
+            .split('-')
+            .map(|p| p.parse::<i32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| InputError::CannotParse(value.clone()))?;
+        match parts[..] {
+            [year, month, day] => Date::new(year, month, day),
+            _ => None,
+        }
+        .ok_or(InputError::CannotParse(value))
+    }
+}
+
+/// The stay from the move-in day to the move-out day, both included.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Stay {
+    pub move_in: Date,
+    pub move_out: Date,
+}
+
+impl Stay {
+    /// Fills in the missing date from `lease_period` months, or days if `in_days` is set.
+    /// `None` if neither date is given.
+    pub fn new(move_in: Option<Date>, move_out: Option<Date>, lease_period: i32, in_days: bool) -> Option<Stay> {
+        let (move_in, move_out) = match (move_in, move_out, in_days) {
+            (Some(i), Some(o), _) => (i, o),
+            (Some(i), None, false) => (i, i.add_months(lease_period).prev_day()),
+            (Some(i), None, true) => (i, i.add_days(lease_period - 1)),
+            (None, Some(o), false) => (o.next_day().add_months(-lease_period), o),
+            (None, Some(o), true) => (o.add_days(1 - lease_period), o),
+            (None, None, _) => return None,
+        };
+        Some(Stay { move_in, move_out })
+    }
+
+    /// Days of the stay, including both the move-in and the move-out days.
+    pub fn days(&self) -> i32 {
+        self.move_out.days_since(self.move_in) + 1
+    }
+
+    /// Months counted from the move-in day, a started month counting as a whole one.
+    /// This is what renewals are based on.
+    pub fn lease_months(&self) -> i32 {
+        let m = self.move_in.month_diff(self.move_out);
+        if self.move_in.add_months(m) <= self.move_out { m + 1 } else { m }
+    }
+
+    /// Calendar months with at least one day of the stay.
+    pub fn calendar_months(&self) -> i32 {
+        self.move_in.month_diff(self.move_out) + 1
+    }
+
+    /// The monthly amount `value` for the days lived in the move-in month (日割り).
+    pub fn first_month(&self, value: i32) -> i32 {
+        let last_day = if self.calendar_months() == 1 { self.move_out.day } else { self.move_in.days_in_month() };
+        prorate(value, last_day - self.move_in.day + 1, self.move_in.days_in_month())
+    }
+
+    /// The monthly amount `value` for the days lived in the move-out month.
+    /// Included in `first_month` when moving out in the move-in month.
+    pub fn last_month(&self, value: i32) -> i32 {
+        if self.calendar_months() == 1 {
+            return 0;
+        }
+        prorate(value, self.move_out.day, self.move_out.days_in_month())
+    }
+
+    /// What to add to `lease_months` full months of a monthly item to pay by the day instead,
+    /// where `first` and `last` are the amounts in force at move-in and at move-out.
+    pub fn adjustment(&self, first: i32, last: i32) -> i32 {
+        let full_months = (self.calendar_months() - 2).max(0);
+        let prorated = self.first_month(first) + self.last_month(last) + full_months * last;
+        prorated - self.lease_months() * last - (first - last)
+    }
+}
+
+/// `value` for `days` out of `days_in_month` days.
+pub fn prorate(value: i32, days: i32, days_in_month: i32) -> i32 {
+    (value as f64 * days as f64 / days_in_month as f64).round() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use crate::date::{Date, Stay};
+
+    fn date(s: &str) -> Date {
+        Date::try_from(s.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(Date::new(2024, 2, 29), Some(date("2024-02-29")));
+        assert!(Date::try_from("2023-02-29".to_string()).is_err());
+        assert!(Date::try_from("2024-13-01".to_string()).is_err());
+        assert!(Date::try_from("".to_string()).is_err());
+        assert_eq!(date("2024-04-05").to_string(), "2024-04-05");
+    }
+
+    #[test]
+    fn months() {
+        assert_eq!(date("2024-01-31").add_months(1), date("2024-02-29"));
+        assert_eq!(date("2024-01-15").add_months(-1), date("2023-12-15"));
+        assert_eq!(date("2024-03-01").prev_day(), date("2024-02-29"));
+        assert_eq!(date("2024-12-31").next_day(), date("2025-01-01"));
+        assert_eq!(date("2024-02-28").add_days(366), date("2025-02-28"));
+        assert_eq!(date("1969-12-31").add_days(-364), date("1969-01-01"));
+    }
+
+    #[test]
+    fn lease_months() {
+        let stay = |i: &str, o: &str| Stay::new(Some(date(i)), Some(date(o)), 0, false).unwrap();
+        assert_eq!(stay("2024-04-15", "2026-04-14").lease_months(), 24);
+        assert_eq!(stay("2024-04-15", "2026-04-15").lease_months(), 25);
+        assert_eq!(stay("2024-04-01", "2024-04-30").lease_months(), 1);
+        assert_eq!(stay("2024-04-15", "2024-04-14").lease_months(), 0);
+        assert_eq!(Stay::new(Some(date("2024-04-15")), None, 24, false).unwrap().move_out, date("2026-04-14"));
+        assert_eq!(Stay::new(None, Some(date("2026-04-14")), 24, false).unwrap().move_in, date("2024-04-15"));
+        assert_eq!(Stay::new(Some(date("2024-02-20")), None, 10, true).unwrap().move_out, date("2024-02-29"));
+        assert_eq!(stay("2024-02-20", "2024-03-01").days(), 11);
+    }
+
+    #[test]
+    fn proration() {
+        let stay = Stay::new(Some(date("2024-04-21")), Some(date("2026-04-20")), 0, false).unwrap();
+        assert_eq!(stay.first_month(60000), 20000);
+        assert_eq!(stay.last_month(60000), 40000);
+        assert_eq!(stay.adjustment(60000, 60000), 0);
+
+        let stay = Stay::new(Some(date("2024-04-21")), Some(date("2024-04-30")), 0, false).unwrap();
+        assert_eq!(stay.first_month(60000), 20000);
+        assert_eq!(stay.last_month(60000), 0);
+        assert_eq!(stay.adjustment(60000, 60000), 20000 - 60000);
+    }
+}
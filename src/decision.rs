@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+
+use crate::CalcError;
+use crate::calculator::{RentCalculator, RentElements, RentItem};
+use crate::cash_flow::cash_flows;
+use crate::constants::UnitId;
+use crate::moving::{CurrentHome, MovingCosts};
+use crate::purchase::Purchase;
+use crate::schedule::term_lengths;
+
+/// The coming renewal of the current apartment, to compare with moving to the property instead.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct Renewal {
+    /// Months until the current contract ends.
+    pub months_left: i32,
+    pub renewal_fee: i32,
+    /// Rent and management fee a month after renewing.
+    pub renewed_rent: i32,
+    /// Months of a contract term, for the renewals after the coming one.
+    pub contract_period: i32,
+    /// Months both paths are compared over. 0 turns the comparison off.
+    pub horizon: i32,
+}
+
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Choice {
+    Stay,
+    Move,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Decision {
+    pub horizon: i32,
+    /// Rent and renewal fees of the current apartment over the horizon.
+    pub stay_cost: i32,
+    /// Rent of the current apartment until the contract ends, moving costs and the property's total after that.
+    pub move_cost: i32,
+    pub cheaper: Choice,
+    /// How much the cheaper path saves.
+    pub saving: i32,
+}
+
+/// Where a segment of a `Plan` is lived.
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Home {
+    Current,
+    /// A lease of the property, which may be a new one of a similar property after moving again.
+    Replacement,
+}
+
+/// One contract term of a `Plan`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    /// Month of the horizon the segment starts from, 0 being now.
+    pub start: i32,
+    pub months: i32,
+    pub home: Home,
+    /// The segment starts with a move, whose costs are included.
+    pub moved: bool,
+    pub cost: i32,
+}
+
+/// The cheapest sequence of renewing and moving over the horizon.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    pub horizon: i32,
+    pub total: i32,
+    pub segments: Vec<Segment>,
+}
+
+impl Renewal {
+    fn months_after(&self) -> i32 {
+        (self.horizon - self.months_left).max(0)
+    }
+
+    /// Fails if a horizon is set without the contract period to renew for.
+    pub fn check(&self) -> Result<(), CalcError> {
+        if self.horizon > 0 && self.contract_period <= 0 {
+            return Err(CalcError::InvalidRenewalPeriod);
+        }
+        Ok(())
+    }
+
+    /// Cost of renewing, every term until the horizon, at `rent` until the coming renewal.
+    /// The renewal should have passed `check`.
+    pub fn stay_cost(&self, rent: i32) -> i32 {
+        let after = self.months_after();
+        let renewals = if after > 0 { (after - 1) / self.contract_period + 1 } else { 0 };
+        rent * self.months_left.min(self.horizon).max(0) + self.renewed_rent * after + self.renewal_fee * renewals
+    }
+}
+
+/// `e` as the property leased for `months` months, on its own.
+pub fn candidate(e: &RentElements, months: i32) -> RentElements {
+    RentElements {
+        lease_period: RentItem::new(months, UnitId::Month),
+        move_in: None,
+        move_out: None,
+        current_home: CurrentHome::default(),
+        renewal: Renewal::default(),
+        moving: MovingCosts::default(),
+        purchase: Purchase::default(),
+        ..e.clone()
+    }
+}
+
+/// Totals of the property of `e` leased for 0 to `longest` months, worked out from the payments
+/// of the longest lease: a shorter lease pays the same months up to its end, then the move-out costs,
+/// and pays back free rent when it ends before the minimum stay.
+pub fn lease_totals(e: &RentElements, longest: i32) -> Vec<i32> {
+    let longest_lease = candidate(e, longest);
+    let flows = cash_flows(&longest_lease);
+    let without_free_rent = cash_flows(&RentElements {
+        free_rent_period: RentItem::new(0, UnitId::Month),
+        ..longest_lease.clone()
+    });
+    let move_out = flows[longest as usize];
+
+    let mut totals = vec![0];
+    let (mut paid, mut free) = (0, 0);
+    for n in 1..=longest {
+        let m = n as usize - 1;
+        paid += flows[m];
+        free += without_free_rent[m] - flows[m];
+        let clawback = if longest_lease.free_rent_clawed_back(n) { free } else { 0 };
+        totals.push((paid + move_out + clawback) as i32);
+    }
+    totals
+}
+
+/// Compares renewing the current apartment of `e` with moving to the property of `e`
+/// when the current contract ends. Notice is assumed to be given in time, so the rents do not overlap.
+pub fn compare(e: &RentElements) -> Result<Decision, CalcError> {
+    let renewal = e.renewal;
+    renewal.check()?;
+    if renewal.months_after() <= 0 {
+        return Err(CalcError::HorizonTooShort);
+    }
+    let rent = e.current_home.rent;
+    let stay_cost = renewal.stay_cost(rent);
+    let move_cost = rent * renewal.months_left.max(0)
+        + e.moving.total()
+        + RentCalculator {}.calc(candidate(e, renewal.months_after()))?.total;
+    Ok(Decision {
+        horizon: renewal.horizon,
+        stay_cost,
+        move_cost,
+        cheaper: if move_cost < stay_cost { Choice::Move } else { Choice::Stay },
+        saving: (stay_cost - move_cost).abs(),
+    })
+}
+
+/// Finds the cheapest plan over the horizon, where at every renewal, of the current apartment
+/// or of the property, one can either renew or move to the property (again).
+/// Each move costs the moving costs, whether counted in the property's total or not, and its initial fees.
+pub fn plan(e: &RentElements) -> Result<Plan, CalcError> {
+    let renewal = e.renewal;
+    renewal.check()?;
+    let horizon = renewal.horizon;
+    if renewal.months_after() <= 0 {
+        return Err(CalcError::HorizonTooShort);
+    }
+    let first_move = renewal.months_left.max(0);
+    // totals[n]: the property leased for n months
+    let totals = lease_totals(e, horizon - first_move);
+    let period = e.contract_period.get(0, 0);
+    let moving_cost = e.moving.total();
+
+    // best[t]: cheapest cost from moving in at month t to the horizon, and the months to stay
+    let mut best = vec![(0, 0); horizon as usize + 1];
+    for t in (first_move..horizon).rev() {
+        let left = horizon - t;
+        best[t as usize] = (1..)
+            .map(|k| k * period)
+            .take_while(|n| *n < left)
+            .chain(Some(left))
+            .map(|n| (moving_cost + totals[n as usize] + best[(t + n) as usize].0, n))
+            .min()
+            .unwrap_or((0, left));
+    }
+
+    let rent = e.current_home.rent;
+    let current_period = renewal.contract_period;
+    // month to move out of the current apartment, if ever
+    let move_out = (0..)
+        .map(|k| first_move + k * current_period)
+        .take_while(|r| *r < horizon)
+        .map(|r| (Renewal { horizon: r, ..renewal }.stay_cost(rent) + best[r as usize].0, Some(r)))
+        .chain(Some((renewal.stay_cost(rent), None)))
+        .min_by_key(|(cost, _)| *cost)
+        .map(|(_, r)| r)
+        .unwrap_or(None);
+
+    let mut segments = vec![];
+    let stay_until = move_out.unwrap_or(horizon);
+    if first_move > 0 {
+        segments.push(Segment {
+            start: 0, months: first_move.min(horizon), home: Home::Current, moved: false, cost: rent * first_move.min(horizon),
+        });
+    }
+    let mut start = first_move;
+    while start < stay_until {
+        let months = current_period.min(stay_until - start);
+        segments.push(Segment {
+            start, months, home: Home::Current, moved: false, cost: renewal.renewed_rent * months + renewal.renewal_fee,
+        });
+        start += months;
+    }
+    while start < horizon {
+        let n = best[start as usize].1;
+        let mut end = 0;
+        for (k, months) in term_lengths(n, period).into_iter().enumerate() {
+            let moving = if k == 0 { moving_cost } else { 0 };
+            segments.push(Segment {
+                start: start + end,
+                months,
+                home: Home::Replacement,
+                moved: k == 0,
+                cost: moving + totals[(end + months) as usize] - totals[end as usize],
+            });
+            end += months;
+        }
+        start += n;
+    }
+    Ok(Plan { horizon, total: segments.iter().map(|s| s.cost).sum(), segments })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CalcError;
+    use crate::calculator::{RentCalculator, RentElements, RentItem};
+    use crate::constants::{MovingKind, UnitId};
+    use crate::decision::{Choice, Home, Renewal, Segment, candidate, compare, lease_totals, plan};
+    use crate::moving::{CurrentHome, MovingCosts, MovingItem};
+
+    #[test]
+    fn stay_cost() {
+        let renewal = Renewal {
+            months_left: 2, renewal_fee: 80000, renewed_rent: 82000, contract_period: 24, horizon: 24,
+        };
+        assert_eq!(renewal.stay_cost(80000), 80000 * 2 + 82000 * 22 + 80000);
+        assert_eq!(Renewal { horizon: 27, ..renewal }.stay_cost(80000), 80000 * 2 + 82000 * 25 + 80000 * 2);
+        assert_eq!(Renewal { horizon: 2, ..renewal }.stay_cost(80000), 80000 * 2);
+    }
+
+    #[test]
+    fn stay_or_move() {
+        let e = RentElements {
+            current_home: CurrentHome { rent: 80000, ..Default::default() },
+            renewal: Renewal {
+                months_left: 2, renewal_fee: 80000, renewed_rent: 80000, contract_period: 24, horizon: 26,
+            },
+            moving: MovingCosts { items: vec![MovingItem { kind: MovingKind::Movers, value: 100000 }], in_total: true },
+            ..Default::default()
+        };
+        let candidate = RentElements { lease_period: RentItem::new(24, UnitId::Month), ..Default::default() };
+        let candidate_total = RentCalculator {}.calc(candidate).unwrap().total;
+
+        let d = compare(&e).unwrap();
+        assert_eq!(d.stay_cost, 80000 * 26 + 80000);
+        assert_eq!(d.move_cost, 80000 * 2 + 100000 + candidate_total);
+        assert_eq!(d.cheaper, if d.move_cost < d.stay_cost { Choice::Move } else { Choice::Stay });
+        assert_eq!(d.saving, (d.stay_cost - d.move_cost).abs());
+
+        let e = RentElements { renewal: Renewal { horizon: 2, ..e.renewal }, ..e };
+        assert!(compare(&e).is_err());
+    }
+
+    #[test]
+    fn renewal_without_contract_period() {
+        let e = RentElements {
+            current_home: CurrentHome { rent: 80000, ..Default::default() },
+            renewal: Renewal {
+                months_left: 2, renewal_fee: 80000, renewed_rent: 80000, horizon: 26, ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(e.renewal.contract_period, 0);
+        assert!(matches!(compare(&e), Err(CalcError::InvalidRenewalPeriod)));
+        assert!(matches!(plan(&e), Err(CalcError::InvalidRenewalPeriod)));
+        assert!(matches!(RentCalculator {}.calc(e.clone()), Err(CalcError::InvalidRenewalPeriod)));
+
+        // without a horizon the renewal is not used
+        let e = RentElements { renewal: Renewal { horizon: 0, ..e.renewal }, ..e };
+        assert!(RentCalculator {}.calc(e).is_ok());
+    }
+
+    #[test]
+    fn totals_of_shorter_leases() {
+        let mut e = RentElements {
+            cleaning_fee: RentItem::new(30000, UnitId::OneShotYen),
+            contract_update_fee: RentItem::new(50000, UnitId::EveryContractUpdateYen),
+            guarantee_fee: RentItem::new(10000, UnitId::YearlyYen),
+            free_rent_period: RentItem::new(2, UnitId::Month),
+            ..Default::default()
+        };
+        e.free_rent.min_stay = 12;
+        let totals = lease_totals(&e, 40);
+        assert_eq!(totals.len(), 41);
+        for n in 1..=40 {
+            assert_eq!(totals[n as usize], RentCalculator {}.calc(candidate(&e, n)).unwrap().total, "{} months", n);
+        }
+    }
+
+    #[test]
+    fn cheapest_plan() {
+        let e = RentElements {
+            current_home: CurrentHome { rent: 80000, ..Default::default() },
+            renewal: Renewal {
+                months_left: 2, renewal_fee: 80000, renewed_rent: 80000, contract_period: 24, horizon: 60,
+            },
+            moving: MovingCosts { items: vec![MovingItem { kind: MovingKind::Movers, value: 100000 }], in_total: true },
+            ..Default::default()
+        };
+        let p = plan(&e).unwrap();
+        assert_eq!(p.segments.iter().map(|s| s.months).sum::<i32>(), 60);
+        assert_eq!(p.total, p.segments.iter().map(|s| s.cost).sum::<i32>());
+        assert_eq!(p.segments[0], Segment { start: 0, months: 2, home: Home::Current, moved: false, cost: 160000 });
+        // never worse than either single decision
+        let d = compare(&e).unwrap();
+        assert!(p.total <= d.stay_cost.min(d.move_cost));
+
+        // a far more expensive current apartment is left at the first chance
+        let e = RentElements { renewal: Renewal { renewed_rent: 500000, ..e.renewal }, ..e };
+        let p = plan(&e).unwrap();
+        assert_eq!(p.segments[1].home, Home::Replacement);
+        assert!(p.segments[1].moved);
+        assert_eq!(p.total, compare(&e).unwrap().move_cost);
+    }
+}
@@ -1,13 +1,29 @@
 use std::fmt;
+use std::rc::Rc;
 
+use strum::IntoEnumIterator;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
 use web_sys::Document;
 use web_sys::Element;
+use web_sys::HtmlInputElement;
 
-use crate::constants::UnitId;
+use crate::affordability::Income;
+use crate::area::Area;
+use crate::calculator::{FreeRent, RentItem as CalcRentItem};
+use crate::constants::{InputId, ItemKind, MovingKind, RunningKind, UnitId};
+use crate::home_office::{HomeOffice, item_percent_id};
+use crate::i18n::Catalog;
+use crate::decision::Renewal;
+use crate::moving::{CurrentHome, MovingCosts};
+use crate::purchase::Purchase;
+use crate::running_costs::RunningCosts;
+use crate::schedule::{Change, TermChange};
+use crate::split::{Share, Split, SplitRule};
+use crate::subsidy::{Subsidy, paid_item_id};
 use crate::utils::{HtmlAttr, make_tag};
+use crate::yen::{format_yen, parse_yen};
 
 #[wasm_bindgen]
 pub fn toggle(document: &Document, id: &str) {
@@ -42,9 +58,9 @@ impl fmt::Display for HtmlId {
     }
 }
 
-impl Into<String> for HtmlId {
-    fn into(self) -> String {
-        self.0
+impl From<HtmlId> for String {
+    fn from(val: HtmlId) -> Self {
+        val.0
     }
 }
 
@@ -53,11 +69,11 @@ pub trait HtmlItem {
 }
 
 pub struct Forms {
-    items: Vec<RentItem>,
+    items: Vec<Box<dyn HtmlItem>>,
 }
 
 impl Forms {
-    pub fn new(items: Vec<RentItem>) -> Forms {
+    pub fn new(items: Vec<Box<dyn HtmlItem>>) -> Forms {
         Forms { items }
     }
 }
@@ -67,7 +83,7 @@ impl HtmlItem for Forms {
         let t: Result<Vec<Vec<Element>>, JsValue> = self
             .items
             .iter()
-            .map(|item| item.to_html(&document))
+            .map(|item| item.to_html(document))
             .collect();
         t.map(|r| r.into_iter().flatten().collect())
     }
@@ -76,13 +92,31 @@ impl HtmlItem for Forms {
 pub struct RentItem {
     label: LabelForInputItem,
     input: InputItem,
-    unit_info: (Vec<UnitId>, HtmlId),
+    unit_info: (Vec<(UnitId, &'static str)>, HtmlId),
+    selected_unit: UnitId,
     detail: DetailForInputItem,
+    interval: Option<IntervalInput>,
+    components: Option<ComponentsInput>,
+}
+
+/// Extra charges of the item, each with its own unit.
+struct ComponentsInput {
+    rows_id: HtmlId,
+    initial: Vec<CalcRentItem>,
+    add_label: &'static str,
+    interval_suffix: &'static str,
+}
+
+/// The N of `UnitId::EveryNMonthsYen`, shown only while that unit is selected.
+struct IntervalInput {
+    id: HtmlId,
+    value: i32,
+    suffix: &'static str,
 }
 
 impl HtmlItem for RentItem {
     fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
-        let input_wrapper = make_tag(&document, "div",
+        let input_wrapper = make_tag(document, "div",
                                      vec![HtmlAttr { name: "class", value: "form-row" }],
                                      None, None,
         )?;
@@ -95,33 +129,46 @@ impl HtmlItem for RentItem {
 
         // add unit form
         {
-            let unit_wrapper = make_tag(&document, "div",
+            let unit_wrapper = make_tag(document, "div",
                                         vec![HtmlAttr { name: "class", value: "form-group col-md-6" }],
                                         None, Some(&input_wrapper),
             )?;
 
-            let unit_selector = make_tag(&document, "select",
+            let unit_selector = make_tag(document, "select",
                                          vec![
                                              HtmlAttr { name: "id", value: &self.unit_info.1.to_string() },
                                              HtmlAttr { name: "class", value: "custom-select" },
                                          ],
                                          None, Some(&unit_wrapper))?;
 
-            self.unit_info.0.iter().map(|e|
-                make_tag(&document, "option",
-                         vec![HtmlAttr { name: "value", value: &format!("{}", *e as i32) }],
-                         Some(e.to_string()), Some(&unit_selector),
-                ))
+            self.unit_info.0.iter().map(|(e, label)| {
+                let value = format!("{}", *e as i32);
+                let mut attr = vec![HtmlAttr { name: "value", value: &value }];
+                if *e == self.selected_unit {
+                    attr.push(HtmlAttr { name: "selected", value: "" });
+                }
+                make_tag(document, "option", attr, Some(label), Some(&unit_selector))
+            })
                 .collect::<Result<Vec<_>, _>>()?;
+
+            if let Some(interval) = &self.interval {
+                self.add_interval(document, &input_wrapper, &unit_selector, interval)?;
+            }
         }
 
         let detail = self.detail.to_html(document)?;
         let label = self.label.to_html(document)?;
 
+        let components = match &self.components {
+            Some(c) => self.add_components(document, c)?,
+            None => vec![],
+        };
+
         Ok(label
             .into_iter()
-            .chain(detail.into_iter())
+            .chain(detail)
             .chain(vec![input_wrapper])
+            .chain(components)
             .collect())
     }
 }
@@ -130,6 +177,13 @@ pub struct InputValidation {
     pub default_value: Option<u32>,
     pub min: Option<u32>,
     pub required: Option<bool>,
+    pub yen: Option<YenInput>,
+}
+
+/// Makes the input a text field accepting notations such as `5.2万` or `¥52,000`,
+/// with a preview of the amount read.
+pub struct YenInput {
+    pub invalid_message: &'static str,
 }
 
 impl Default for InputValidation {
@@ -138,14 +192,90 @@ impl Default for InputValidation {
             default_value: Some(0),
             min: Some(0),
             required: Some(true),
+            yen: None,
         }
     }
 }
 
 impl RentItem {
+    fn add_interval(&self, document: &Document, wrapper: &Element,
+                    unit_selector: &Element, interval: &IntervalInput) -> Result<(), JsValue> {
+        let group = make_tag(document, "div",
+                             vec![HtmlAttr { name: "class", value: "form-group col-md-6 offset-md-6" }],
+                             None, Some(wrapper))?;
+        let input_group = make_tag(document, "div",
+                                   vec![HtmlAttr { name: "class", value: "input-group" }],
+                                   None, Some(&group))?;
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "number" },
+                     HtmlAttr { name: "id", value: &format!("{}", &interval.id) },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "min", value: "1" },
+                     HtmlAttr { name: "required", value: "" },
+                     HtmlAttr { name: "value", value: &format!("{}", interval.value) },
+                 ], None, Some(&input_group))?;
+        let append = make_tag(document, "div",
+                              vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                              None, Some(&input_group))?;
+        make_tag(document, "span",
+                 vec![HtmlAttr { name: "class", value: "input-group-text" }],
+                 Some(interval.suffix), Some(&append))?;
+
+        show_interval_on_select(unit_selector, group.dyn_into::<web_sys::HtmlElement>()?)
+    }
+
+    /// Adds rows for extra charges, with a button adding more.
+    pub fn with_components(mut self, rows_id_raw: &str, initial: Vec<CalcRentItem>,
+                           add_label: &'static str, interval_suffix: &'static str) -> Self {
+        self.components = Some(ComponentsInput {
+            rows_id: HtmlId(rows_id_raw.to_string()),
+            initial,
+            add_label,
+            interval_suffix,
+        });
+        self
+    }
+
+    fn add_components(&self, document: &Document, components: &ComponentsInput) -> Result<Vec<Element>, JsValue> {
+        let rows = make_tag(document, "div",
+                            vec![HtmlAttr { name: "id", value: &format!("{}", &components.rows_id) }],
+                            None, None)?;
+        let units = Rc::new(self.unit_info.0.clone());
+        for charge in components.initial.iter() {
+            make_component_row(document, &rows, &units, components.interval_suffix, Some(charge))?;
+        }
+
+        let add_button = make_tag(document, "button",
+                                  vec![
+                                      HtmlAttr { name: "type", value: "button" },
+                                      HtmlAttr { name: "class", value: "btn btn-outline-secondary btn-sm mb-3" },
+                                  ], Some(components.add_label), None)?;
+        {
+            let document = document.clone();
+            let rows = rows.clone();
+            let interval_suffix = components.interval_suffix;
+            let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+                if let Err(e) = make_component_row(&document, &rows, &units, interval_suffix, None) {
+                    web_sys::console::error_1(&e);
+                }
+            }) as Box<dyn FnMut(_)>);
+            add_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+            closure.forget();
+        }
+        Ok(vec![rows, add_button])
+    }
+
+    /// Adds the input for the N of `UnitId::EveryNMonthsYen`.
+    pub fn with_interval(mut self, id_raw: &str, value: i32, suffix: &'static str) -> Self {
+        self.interval = Some(IntervalInput { id: HtmlId(id_raw.to_string()), value, suffix });
+        self
+    }
+
     pub fn new(
         item_text: &str,
-        unit: Vec<UnitId>,
+        unit: Vec<(UnitId, &'static str)>,
+        selected_unit: UnitId,
         unit_id_raw: &str,
         detail_text: &str,
         input_id_raw: &str,
@@ -166,10 +296,13 @@ impl RentItem {
                 detail_id: detail_id.clone(),
             },
             unit_info: (unit, unit_id),
+            selected_unit,
             detail: DetailForInputItem {
                 id: detail_id,
                 text: detail_text.to_string(),
             },
+            interval: None,
+            components: None,
         }
     }
 }
@@ -182,15 +315,15 @@ struct LabelForInputItem {
 
 impl HtmlItem for LabelForInputItem {
     fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
-        let label = make_tag(&document, "label",
+        let label = make_tag(document, "label",
                              vec![HtmlAttr { name: "for", value: &format!("{}", self.input_id) }],
                              None, None)?;
 
-        make_tag(&document, "span", vec![
+        make_tag(document, "span", vec![
             HtmlAttr { name: "class", value: "font-weight-bold" }
         ], Some(&self.text), Some(&label))?;
 
-        let info_wrapper = make_tag(&document, "span", vec![], Some(" "), Some(&label))?;
+        let info_wrapper = make_tag(document, "span", vec![], Some(" "), Some(&label))?;
 
         {
             let detail_id = format!("{}", self.detail_id);
@@ -205,12 +338,12 @@ impl HtmlItem for LabelForInputItem {
                 .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
             closure.forget();
         }
-        make_tag(&document, "i",
+        make_tag(document, "i",
                  vec![
                      HtmlAttr { name: "class", value: "fas fa-info-circle" },
                  ],
                  None, Some(&info_wrapper))?;
-        make_tag(&document, "span", vec![],
+        make_tag(document, "span", vec![],
                  Some(": "), Some(&label))?;
 
         Ok(vec![label])
@@ -225,20 +358,21 @@ struct InputItem {
 
 impl HtmlItem for InputItem {
     fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
-        let wrapper = make_tag(&document, "div",
+        let wrapper = make_tag(document, "div",
                                vec![HtmlAttr { name: "class", value: "form-group col-md-6" }],
                                None, None)?;
 
         {
-            let input = make_tag(&document, "input",
+            let input_type = if self.input_validation.yen.is_some() { "text" } else { "number" };
+            let input = make_tag(document, "input",
                                  vec![
-                                     HtmlAttr { name: "type", value: "number" },
+                                     HtmlAttr { name: "type", value: input_type },
                                      HtmlAttr { name: "name", value: &format!("{}", &self.id) },
                                      HtmlAttr { name: "class", value: "form-control" },
                                      HtmlAttr { name: "aria-describedby", value: &format!("{}", &self.detail_id) },
                                      HtmlAttr { name: "id", value: &format!("{}", &self.id) }
                                  ], None, Some(&wrapper))?;
-            let input: web_sys::HtmlInputElement = input.dyn_into::<web_sys::HtmlInputElement>()?;
+            let input: HtmlInputElement = input.dyn_into::<HtmlInputElement>()?;
 
             if let Some(v) = self.input_validation.default_value {
                 input.set_default_value(&format!("{}", v));
@@ -247,13 +381,49 @@ impl HtmlItem for InputItem {
                 input.set_attribute("min", &format!("{}", &v))?;
             }
             if self.input_validation.required.is_some() {
-                input.set_attribute("required", &"")?;
+                input.set_attribute("required", "")?;
+            }
+            if let Some(yen) = &self.input_validation.yen {
+                input.set_attribute("inputmode", "decimal")?;
+                self.add_yen_preview(document, &wrapper, input, yen.invalid_message)?;
             }
         }
         Ok(vec![wrapper])
     }
 }
 
+impl InputItem {
+    fn add_yen_preview(&self, document: &Document, wrapper: &Element,
+                       input: HtmlInputElement, invalid_message: &'static str) -> Result<(), JsValue> {
+        let preview = make_tag(document, "small",
+                               vec![
+                                   HtmlAttr { name: "id", value: &format!("{}_preview", &self.id) },
+                                   HtmlAttr { name: "class", value: "form-text text-muted" },
+                               ], None, Some(wrapper))?;
+
+        let update = move |input: &HtmlInputElement| {
+            match parse_yen(&input.value()) {
+                Some(v) => {
+                    input.set_custom_validity("");
+                    preview.set_inner_html(&format!("= ¥{}", format_yen(v)));
+                }
+                None => {
+                    input.set_custom_validity(invalid_message);
+                    preview.set_inner_html("= ?");
+                }
+            }
+        };
+        update(&input);
+
+        let input_ref = input.clone();
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| update(&input_ref))
+            as Box<dyn FnMut(_)>);
+        input.add_event_listener_with_callback("input", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+        Ok(())
+    }
+}
+
 struct DetailForInputItem {
     id: HtmlId,
     text: String,
@@ -261,7 +431,7 @@ struct DetailForInputItem {
 
 impl HtmlItem for DetailForInputItem {
     fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
-        let small = make_tag(&document, "small",
+        let small = make_tag(document, "small",
                              vec![
                                  HtmlAttr { name: "id", value: &format!("{}", &self.id) },
                                  HtmlAttr { name: "style", value: "display: none;" },
@@ -271,3 +441,1346 @@ impl HtmlItem for DetailForInputItem {
         Ok(vec![small])
     }
 }
+
+/// A labelled choice among fixed options, such as the contract type.
+pub struct SelectItem {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    id: HtmlId,
+    options: Vec<(i32, &'static str)>,
+    selected: i32,
+}
+
+impl SelectItem {
+    pub fn new(text: &str, detail_text: &str, id_raw: &str,
+               options: Vec<(i32, &'static str)>, selected: i32) -> Self {
+        let id = HtmlId(id_raw.to_string());
+        let detail_id = HtmlId(format!("{}_detail", id_raw));
+        SelectItem {
+            label: LabelForInputItem {
+                text: text.to_string(),
+                input_id: id.clone(),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: detail_text.to_string(),
+            },
+            id,
+            options,
+            selected,
+        }
+    }
+}
+
+impl HtmlItem for SelectItem {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-group" }],
+                               None, None)?;
+        let select = make_tag(document, "select",
+                              vec![
+                                  HtmlAttr { name: "id", value: &format!("{}", &self.id) },
+                                  HtmlAttr { name: "class", value: "custom-select" },
+                              ], None, Some(&wrapper))?;
+        for (value, label) in self.options.iter() {
+            let value_str = format!("{}", value);
+            let mut attr = vec![HtmlAttr { name: "value", value: &value_str }];
+            if *value == self.selected {
+                attr.push(HtmlAttr { name: "selected", value: "" });
+            }
+            make_tag(document, "option", attr, Some(label), Some(&select))?;
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// An optional date, left empty by default.
+pub struct DateItem {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    id: HtmlId,
+    value: Option<String>,
+}
+
+impl DateItem {
+    pub fn new(text: &str, detail_text: &str, id_raw: &str, value: Option<String>) -> Self {
+        let id = HtmlId(id_raw.to_string());
+        let detail_id = HtmlId(format!("{}_detail", id_raw));
+        DateItem {
+            label: LabelForInputItem {
+                text: text.to_string(),
+                input_id: id.clone(),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: detail_text.to_string(),
+            },
+            id,
+            value,
+        }
+    }
+}
+
+impl HtmlItem for DateItem {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-group" }],
+                               None, None)?;
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "date" },
+                     HtmlAttr { name: "id", value: &format!("{}", &self.id) },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "aria-describedby", value: &format!("{}", &self.label.detail_id) },
+                     HtmlAttr { name: "value", value: self.value.as_deref().unwrap_or("") },
+                 ], None, Some(&wrapper))?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// A percentage that may have decimals, e.g. an interest rate.
+pub struct PercentItem {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    id: HtmlId,
+    value: f64,
+}
+
+impl PercentItem {
+    pub fn new(text: &str, detail_text: &str, id_raw: &str, value: f64) -> Self {
+        let id = HtmlId(id_raw.to_string());
+        let detail_id = HtmlId(format!("{}_detail", id_raw));
+        PercentItem {
+            label: LabelForInputItem {
+                text: text.to_string(),
+                input_id: id.clone(),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: detail_text.to_string(),
+            },
+            id,
+            value,
+        }
+    }
+}
+
+impl HtmlItem for PercentItem {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-group" }],
+                               None, None)?;
+        let group = make_tag(document, "div",
+                             vec![HtmlAttr { name: "class", value: "input-group" }],
+                             None, Some(&wrapper))?;
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "number" },
+                     HtmlAttr { name: "id", value: &format!("{}", &self.id) },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "aria-describedby", value: &format!("{}", &self.label.detail_id) },
+                     HtmlAttr { name: "min", value: "0" },
+                     HtmlAttr { name: "step", value: "0.1" },
+                     HtmlAttr { name: "required", value: "" },
+                     HtmlAttr { name: "value", value: &format!("{}", self.value) },
+                 ], None, Some(&group))?;
+        let append = make_tag(document, "div",
+                              vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                              None, Some(&group))?;
+        make_tag(document, "span",
+                 vec![HtmlAttr { name: "class", value: "input-group-text" }],
+                 Some("%"), Some(&append))?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// Conditions of the free rent months, read back by `RentElements::build`.
+pub struct FreeRentForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: FreeRent,
+    catalog: &'static Catalog,
+}
+
+impl FreeRentForm {
+    pub fn new(initial: FreeRent, catalog: &'static Catalog) -> Self {
+        let id = InputId::FreeRentStart.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        FreeRentForm {
+            label: LabelForInputItem {
+                text: catalog.free_rent_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.free_rent_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for FreeRentForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+
+        make_number_group(document, &col("form-group col-md-4")?, InputId::FreeRentStart,
+                          self.initial.start + 1, 1, self.catalog.free_rent_start)?;
+        make_number_group(document, &col("form-group col-md-4")?, InputId::FreeRentMinStay,
+                          self.initial.min_stay, 0, self.catalog.free_rent_min_stay)?;
+
+        let check = make_tag(document, "div",
+                             vec![HtmlAttr { name: "class", value: "custom-control custom-checkbox" }],
+                             None, Some(&col("form-group col-md-4")?))?;
+        let id = InputId::FreeRentCoversManagementFee.to_string();
+        let input = make_tag(document, "input",
+                             vec![
+                                 HtmlAttr { name: "type", value: "checkbox" },
+                                 HtmlAttr { name: "class", value: "custom-control-input" },
+                                 HtmlAttr { name: "id", value: &id },
+                             ], None, Some(&check))?;
+        input.dyn_into::<HtmlInputElement>()?.set_checked(self.initial.covers_management_fee);
+        make_tag(document, "label",
+                 vec![
+                     HtmlAttr { name: "class", value: "custom-control-label" },
+                     HtmlAttr { name: "for", value: &id },
+                 ], Some(self.catalog.free_rent_covers_management_fee), Some(&check))?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// The apartment being left, read back by `RentElements::build`.
+pub struct CurrentHomeForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: CurrentHome,
+    catalog: &'static Catalog,
+}
+
+impl CurrentHomeForm {
+    pub fn new(initial: CurrentHome, catalog: &'static Catalog) -> Self {
+        let id = InputId::CurrentRent.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        CurrentHomeForm {
+            label: LabelForInputItem {
+                text: catalog.current_home_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.current_home_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for CurrentHomeForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+
+        make_number_group(document, &col("form-group col-md-3")?, InputId::CurrentRent,
+                          self.initial.rent, 0, self.catalog.current_rent)?;
+        make_number_group(document, &col("form-group col-md-3")?, InputId::CurrentNotice,
+                          self.initial.notice_months, 0, self.catalog.current_notice)?;
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "date" },
+                     HtmlAttr { name: "id", value: &InputId::CurrentMoveOut.to_string() },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "aria-label", value: self.catalog.current_move_out },
+                     HtmlAttr { name: "title", value: self.catalog.current_move_out },
+                     HtmlAttr { name: "value", value: &self.initial.move_out.map(String::from).unwrap_or_default() },
+                 ], None, Some(&col("form-group col-md-3")?))?;
+
+        let check = make_tag(document, "div",
+                             vec![HtmlAttr { name: "class", value: "custom-control custom-checkbox" }],
+                             None, Some(&col("form-group col-md-3")?))?;
+        let id = InputId::OverlapInTotal.to_string();
+        let input = make_tag(document, "input",
+                             vec![
+                                 HtmlAttr { name: "type", value: "checkbox" },
+                                 HtmlAttr { name: "class", value: "custom-control-input" },
+                                 HtmlAttr { name: "id", value: &id },
+                             ], None, Some(&check))?;
+        input.dyn_into::<HtmlInputElement>()?.set_checked(self.initial.in_total);
+        make_tag(document, "label",
+                 vec![
+                     HtmlAttr { name: "class", value: "custom-control-label" },
+                     HtmlAttr { name: "for", value: &id },
+                 ], Some(self.catalog.overlap_in_total), Some(&check))?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// The coming renewal of the current apartment, read back by `RentElements::build`.
+pub struct RenewalForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: Renewal,
+    catalog: &'static Catalog,
+}
+
+impl RenewalForm {
+    pub fn new(initial: Renewal, catalog: &'static Catalog) -> Self {
+        let id = InputId::RenewalMonthsLeft.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        RenewalForm {
+            label: LabelForInputItem {
+                text: catalog.renewal_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.renewal_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for RenewalForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let (i, c) = (&self.initial, self.catalog);
+        for (id, value, min, suffix) in [
+            (InputId::RenewalMonthsLeft, i.months_left, 0, c.renewal_months_left),
+            (InputId::RenewalFee, i.renewal_fee, 0, c.renewal_fee),
+            (InputId::RenewedRent, i.renewed_rent, 0, c.renewed_rent),
+            (InputId::RenewalContractPeriod, i.contract_period, 1, c.renewal_contract_period),
+            (InputId::RenewalHorizon, i.horizon, 0, c.renewal_horizon),
+        ] {
+            let col = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-group col-md-4" }],
+                               None, Some(&row))?;
+            make_number_group(document, &col, id, value, min, suffix)?;
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// A purchase to compare renting with, read back by `RentElements::build`.
+pub struct PurchaseForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: Purchase,
+    catalog: &'static Catalog,
+}
+
+impl PurchaseForm {
+    pub fn new(initial: Purchase, catalog: &'static Catalog) -> Self {
+        let id = InputId::PurchasePrice.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        PurchaseForm {
+            label: LabelForInputItem {
+                text: catalog.purchase_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.purchase_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for PurchaseForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let col = || make_tag(document, "div",
+                              vec![HtmlAttr { name: "class", value: "form-group col-md-6" }],
+                              None, Some(&row));
+        let (i, c) = (&self.initial, self.catalog);
+        for (id, value, suffix) in [
+            (InputId::PurchasePrice, i.price, c.purchase_price),
+            (InputId::DownPayment, i.down_payment, c.down_payment),
+            (InputId::ClosingCosts, i.closing_costs, c.closing_costs),
+            (InputId::ResaleValue, i.resale_value, c.resale_value),
+        ] {
+            make_number_group(document, &col()?, id, value, 0, suffix)?;
+        }
+        make_decimal_group(document, &col()?, InputId::MortgageRate, i.mortgage_rate, "0.01", c.mortgage_rate)?;
+        for (id, value, suffix) in [
+            (InputId::MortgageYears, i.mortgage_years, c.mortgage_years),
+            (InputId::OwnerManagementFee, i.management_fee, c.owner_management_fee),
+            (InputId::RepairReserve, i.repair_reserve, c.repair_reserve),
+            (InputId::PropertyTax, i.property_tax, c.property_tax),
+            (InputId::PurchaseHorizon, i.horizon_years, c.purchase_horizon),
+        ] {
+            make_number_group(document, &col()?, id, value, 0, suffix)?;
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// Costs of moving in, read back by `RentElements::build`.
+pub struct MovingCostsForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: MovingCosts,
+    catalog: &'static Catalog,
+}
+
+impl MovingCostsForm {
+    pub fn new(initial: MovingCosts, catalog: &'static Catalog) -> Self {
+        let id = MovingKind::Movers.input_id().to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        MovingCostsForm {
+            label: LabelForInputItem {
+                text: catalog.moving_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.moving_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for MovingCostsForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div", vec![], None, None)?;
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, Some(&wrapper))?;
+        for kind in MovingKind::iter() {
+            let col = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-group col-md-4" }],
+                               None, Some(&row))?;
+            make_number_group(document, &col, kind.input_id(), self.initial.value(kind), 0,
+                              (self.catalog.moving_kind_name)(kind))?;
+        }
+
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, Some(&wrapper))?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+        let presets = make_tag(document, "button",
+                               vec![
+                                   HtmlAttr { name: "type", value: "button" },
+                                   HtmlAttr { name: "id", value: &InputId::MovingPresets.to_string() },
+                                   HtmlAttr { name: "class", value: "btn btn-outline-secondary" },
+                               ], Some(self.catalog.moving_presets), Some(&col("form-group col-md-4")?))?;
+        fill_presets_on_click(document, &presets, MovingKind::iter().map(|k| (k.input_id(), k.preset())).collect())?;
+
+        let check = make_tag(document, "div",
+                             vec![HtmlAttr { name: "class", value: "custom-control custom-checkbox" }],
+                             None, Some(&col("form-group col-md-8")?))?;
+        let id = InputId::MovingInTotal.to_string();
+        let input = make_tag(document, "input",
+                             vec![
+                                 HtmlAttr { name: "type", value: "checkbox" },
+                                 HtmlAttr { name: "class", value: "custom-control-input" },
+                                 HtmlAttr { name: "id", value: &id },
+                             ], None, Some(&check))?;
+        input.dyn_into::<HtmlInputElement>()?.set_checked(self.initial.in_total);
+        make_tag(document, "label",
+                 vec![
+                     HtmlAttr { name: "class", value: "custom-control-label" },
+                     HtmlAttr { name: "for", value: &id },
+                 ], Some(self.catalog.moving_in_total), Some(&check))?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// Monthly running costs, read back by `RentElements::build`.
+pub struct RunningCostsForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: RunningCosts,
+    catalog: &'static Catalog,
+}
+
+impl RunningCostsForm {
+    pub fn new(initial: RunningCosts, catalog: &'static Catalog) -> Self {
+        let id = RunningKind::Electricity.input_id().to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        RunningCostsForm {
+            label: LabelForInputItem {
+                text: catalog.running_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.running_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for RunningCostsForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div", vec![], None, None)?;
+        for kind in RunningKind::iter() {
+            let item = self.initial.item(kind);
+            let row = make_tag(document, "div",
+                               vec![HtmlAttr { name: "class", value: "form-row" }],
+                               None, Some(&wrapper))?;
+            let col = |class: &str| make_tag(document, "div",
+                                             vec![HtmlAttr { name: "class", value: class }],
+                                             None, Some(&row));
+            make_number_group(document, &col("form-group col-md-6")?, kind.input_id(),
+                              item.map_or(0, |i| i.value), 0, (self.catalog.running_kind_name)(kind))?;
+
+            let check = make_tag(document, "div",
+                                 vec![HtmlAttr { name: "class", value: "custom-control custom-checkbox" }],
+                                 None, Some(&col("form-group col-md-6")?))?;
+            let id = kind.included_input_id().to_string();
+            let input = make_tag(document, "input",
+                                 vec![
+                                     HtmlAttr { name: "type", value: "checkbox" },
+                                     HtmlAttr { name: "class", value: "custom-control-input" },
+                                     HtmlAttr { name: "id", value: &id },
+                                 ], None, Some(&check))?;
+            input.dyn_into::<HtmlInputElement>()?.set_checked(item.is_some_and(|i| i.included));
+            make_tag(document, "label",
+                     vec![
+                         HtmlAttr { name: "class", value: "custom-control-label" },
+                         HtmlAttr { name: "for", value: &id },
+                     ], Some(self.catalog.running_included), Some(&check))?;
+        }
+        let presets = make_tag(document, "button",
+                               vec![
+                                   HtmlAttr { name: "type", value: "button" },
+                                   HtmlAttr { name: "id", value: &InputId::RunningPresets.to_string() },
+                                   HtmlAttr { name: "class", value: "btn btn-outline-secondary mb-3" },
+                               ], Some(self.catalog.running_presets), Some(&wrapper))?;
+        fill_presets_on_click(document, &presets, RunningKind::iter().map(|k| (k.input_id(), k.preset())).collect())?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// Floor area and occupants, read back by `RentElements::build`.
+pub struct AreaForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: (Area, i32),
+    catalog: &'static Catalog,
+}
+
+impl AreaForm {
+    pub fn new(area: Area, occupants: i32, catalog: &'static Catalog) -> Self {
+        let id = InputId::SquareMeters.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        AreaForm {
+            label: LabelForInputItem {
+                text: catalog.area_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.area_detail.to_string(),
+            },
+            initial: (area, occupants),
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for AreaForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+        let (area, occupants) = self.initial;
+        make_decimal_group(document, &col("form-group col-md-4")?, InputId::SquareMeters,
+                           area.square_meters, "0.01", self.catalog.square_meters)?;
+        make_decimal_group(document, &col("form-group col-md-4")?, InputId::Jo,
+                           area.jo, "0.1", self.catalog.jo)?;
+        make_number_group(document, &col("form-group col-md-4")?, InputId::Occupants,
+                          occupants, 1, self.catalog.occupants)?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// Income and budget, read back by `RentElements::build`.
+pub struct IncomeForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    initial: Income,
+    catalog: &'static Catalog,
+}
+
+impl IncomeForm {
+    pub fn new(initial: Income, catalog: &'static Catalog) -> Self {
+        let id = InputId::AnnualIncome.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        IncomeForm {
+            label: LabelForInputItem {
+                text: catalog.income_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.income_detail.to_string(),
+            },
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for IncomeForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, None)?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+        make_number_group(document, &col("form-group col-md-4")?, InputId::AnnualIncome,
+                          self.initial.annual_gross, 0, self.catalog.annual_income)?;
+        make_number_group(document, &col("form-group col-md-4")?, InputId::TakeHomePay,
+                          self.initial.monthly_take_home, 0, self.catalog.take_home_pay)?;
+        let budget = (self.initial.budget_percent * 10.0).round() / 10.0;
+        make_decimal_group(document, &col("form-group col-md-4")?, InputId::BudgetPercent,
+                           budget, "0.1", self.catalog.budget_percent)?;
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![row])
+            .collect())
+    }
+}
+
+/// Employer's housing allowance and corporate lease support, read back by `RentElements::build`.
+pub struct SubsidyForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    items: Vec<(ItemKind, &'static str)>,
+    initial: Subsidy,
+    catalog: &'static Catalog,
+}
+
+impl SubsidyForm {
+    pub fn new(items: Vec<(ItemKind, &'static str)>, initial: Subsidy, catalog: &'static Catalog) -> Self {
+        let id = InputId::HousingAllowance.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        SubsidyForm {
+            label: LabelForInputItem {
+                text: catalog.subsidy_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.subsidy_detail.to_string(),
+            },
+            items,
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for SubsidyForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div", vec![], None, None)?;
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, Some(&wrapper))?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+        make_number_group(document, &col("form-group col-md-6")?, InputId::HousingAllowance,
+                          self.initial.allowance, 0, self.catalog.housing_allowance)?;
+        make_number_group(document, &col("form-group col-md-6")?, InputId::SubsidyRentLimit,
+                          self.initial.rent_limit, 0, self.catalog.subsidy_rent_limit)?;
+        make_decimal_group(document, &col("form-group col-md-6")?, InputId::EmployerRentPercent,
+                           self.initial.rent_percent, "1", self.catalog.employer_rent_percent)?;
+        make_number_group(document, &col("form-group col-md-6")?, InputId::EmployerRentCap,
+                          self.initial.rent_cap, 0, self.catalog.employer_rent_cap)?;
+
+        let items = make_tag(document, "div",
+                             vec![
+                                 HtmlAttr { name: "id", value: &InputId::EmployerPaidItems.to_string() },
+                                 HtmlAttr { name: "class", value: "form-group" },
+                             ], None, Some(&wrapper))?;
+        make_tag(document, "small", vec![HtmlAttr { name: "class", value: "d-block" }],
+                 Some(self.catalog.employer_paid_items), Some(&items))?;
+        for (kind, text) in &self.items {
+            let check = make_tag(document, "div",
+                                 vec![HtmlAttr { name: "class", value: "custom-control custom-checkbox custom-control-inline" }],
+                                 None, Some(&items))?;
+            let id = paid_item_id(*kind);
+            let input = make_tag(document, "input",
+                                 vec![
+                                     HtmlAttr { name: "type", value: "checkbox" },
+                                     HtmlAttr { name: "class", value: "custom-control-input" },
+                                     HtmlAttr { name: "id", value: &id },
+                                 ], None, Some(&check))?;
+            input.dyn_into::<HtmlInputElement>()?.set_checked(self.initial.paid_items.contains(kind));
+            make_tag(document, "label",
+                     vec![
+                         HtmlAttr { name: "class", value: "custom-control-label" },
+                         HtmlAttr { name: "for", value: &id },
+                     ], Some(text), Some(&check))?;
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// Business share of the costs, read back by `RentElements::build`.
+pub struct HomeOfficeForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    items: Vec<(ItemKind, &'static str)>,
+    initial: HomeOffice,
+    catalog: &'static Catalog,
+}
+
+impl HomeOfficeForm {
+    pub fn new(items: Vec<(ItemKind, &'static str)>, initial: HomeOffice, catalog: &'static Catalog) -> Self {
+        let id = InputId::HomeOfficePercent.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        HomeOfficeForm {
+            label: LabelForInputItem {
+                text: catalog.home_office_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.home_office_detail.to_string(),
+            },
+            items,
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for HomeOfficeForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let wrapper = make_tag(document, "div", vec![], None, None)?;
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, Some(&wrapper))?;
+        let col = |class: &str| make_tag(document, "div",
+                                         vec![HtmlAttr { name: "class", value: class }],
+                                         None, Some(&row));
+        make_decimal_group(document, &col("form-group col-md-6")?, InputId::HomeOfficePercent,
+                           self.initial.percent, "1", self.catalog.home_office_percent)?;
+
+        let row = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-row" }],
+                           None, Some(&wrapper))?;
+        for (kind, text) in &self.items {
+            let group = make_tag(document, "div",
+                                 vec![HtmlAttr { name: "class", value: "form-group col-md-4" }],
+                                 None, Some(&row))?;
+            let id = item_percent_id(*kind);
+            make_tag(document, "label",
+                     vec![HtmlAttr { name: "for", value: &id }, HtmlAttr { name: "class", value: "small" }],
+                     Some(text), Some(&group))?;
+            let value = self.initial.items
+                .iter()
+                .rev()
+                .find(|i| i.item == *kind)
+                .map_or(String::new(), |i| format!("{}", i.percent));
+            make_tag(document, "input",
+                     vec![
+                         HtmlAttr { name: "type", value: "number" },
+                         HtmlAttr { name: "id", value: &id },
+                         HtmlAttr { name: "class", value: "form-control form-control-sm" },
+                         HtmlAttr { name: "min", value: "0" },
+                         HtmlAttr { name: "max", value: "100" },
+                         HtmlAttr { name: "placeholder", value: self.catalog.home_office_common },
+                         HtmlAttr { name: "value", value: &value },
+                     ], None, Some(&group))?;
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![wrapper])
+            .collect())
+    }
+}
+
+/// Makes `button` fill the empty or 0 inputs with their preset amount.
+fn fill_presets_on_click(document: &Document, button: &Element, presets: Vec<(InputId, i32)>) -> Result<(), JsValue> {
+    let document = document.clone();
+    let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+        for (id, preset) in &presets {
+            let input = document
+                .get_element_by_id(&id.to_string())
+                .and_then(|e| e.dyn_into::<HtmlInputElement>().ok());
+            if let Some(input) = input.filter(|i| i.value().is_empty() || i.value() == "0") {
+                input.set_value(&format!("{}", preset));
+                // lets live mode notice the new values
+                let init = web_sys::EventInit::new();
+                init.set_bubbles(true);
+                if let Err(e) = web_sys::Event::new_with_event_init_dict("change", &init)
+                    .and_then(|e| input.dispatch_event(&e)) {
+                    web_sys::console::error_1(&e);
+                }
+            }
+        }
+    }) as Box<dyn FnMut(_)>);
+    button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    Ok(())
+}
+
+/// A required number input followed by `suffix`.
+fn make_number_group(document: &Document, parent: &Element, id: InputId,
+                     value: i32, min: i32, suffix: &str) -> Result<(), JsValue> {
+    let group = make_tag(document, "div",
+                         vec![HtmlAttr { name: "class", value: "input-group" }],
+                         None, Some(parent))?;
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "number" },
+                 HtmlAttr { name: "id", value: &id.to_string() },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "min", value: &format!("{}", min) },
+                 HtmlAttr { name: "required", value: "" },
+                 HtmlAttr { name: "value", value: &format!("{}", value) },
+             ], None, Some(&group))?;
+    let append = make_tag(document, "div",
+                          vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                          None, Some(&group))?;
+    make_tag(document, "span",
+             vec![HtmlAttr { name: "class", value: "input-group-text" }],
+             Some(suffix), Some(&append))?;
+    Ok(())
+}
+
+/// Same as `make_number_group` for a number with decimals, 0 or more.
+fn make_decimal_group(document: &Document, parent: &Element, id: InputId,
+                      value: f64, step: &str, suffix: &str) -> Result<(), JsValue> {
+    let group = make_tag(document, "div",
+                         vec![HtmlAttr { name: "class", value: "input-group" }],
+                         None, Some(parent))?;
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "number" },
+                 HtmlAttr { name: "id", value: &id.to_string() },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "min", value: "0" },
+                 HtmlAttr { name: "step", value: step },
+                 HtmlAttr { name: "required", value: "" },
+                 HtmlAttr { name: "value", value: &format!("{}", value) },
+             ], None, Some(&group))?;
+    let append = make_tag(document, "div",
+                          vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                          None, Some(&group))?;
+    make_tag(document, "span",
+             vec![HtmlAttr { name: "class", value: "input-group-text" }],
+             Some(suffix), Some(&append))?;
+    Ok(())
+}
+
+/// Rows of `schedule::TermChange`, read back by `schedule::read_schedule`.
+pub struct ScheduleForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    rows_id: HtmlId,
+    items: Rc<Vec<(ItemKind, &'static str)>>,
+    initial: Vec<TermChange>,
+    catalog: &'static Catalog,
+}
+
+impl ScheduleForm {
+    pub fn new(rows_id_raw: &str, items: Vec<(ItemKind, &'static str)>,
+               initial: Vec<TermChange>, catalog: &'static Catalog) -> Self {
+        let rows_id = HtmlId(rows_id_raw.to_string());
+        let detail_id = HtmlId(format!("{}_detail", rows_id_raw));
+        ScheduleForm {
+            label: LabelForInputItem {
+                text: catalog.schedule_title.to_string(),
+                input_id: rows_id.clone(),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.schedule_detail.to_string(),
+            },
+            rows_id,
+            items: Rc::new(items),
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for ScheduleForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let rows = make_tag(document, "div",
+                            vec![HtmlAttr { name: "id", value: &format!("{}", &self.rows_id) }],
+                            None, None)?;
+        for change in self.initial.iter() {
+            make_schedule_row(document, &rows, &self.items, self.catalog, Some(change))?;
+        }
+
+        let add_button = make_tag(document, "button",
+                                  vec![
+                                      HtmlAttr { name: "type", value: "button" },
+                                      HtmlAttr { name: "class", value: "btn btn-outline-secondary btn-sm mb-3" },
+                                  ], Some(self.catalog.schedule_add), None)?;
+        {
+            let document = document.clone();
+            let rows = rows.clone();
+            let items = self.items.clone();
+            let catalog = self.catalog;
+            let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+                if let Err(e) = make_schedule_row(&document, &rows, &items, catalog, None) {
+                    web_sys::console::error_1(&e);
+                }
+            }) as Box<dyn FnMut(_)>);
+            add_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+            closure.forget();
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![rows, add_button])
+            .collect())
+    }
+}
+
+fn make_schedule_row(document: &Document, rows: &Element, items: &[(ItemKind, &'static str)],
+                     catalog: &'static Catalog, change: Option<&TermChange>) -> Result<Element, JsValue> {
+    let row = make_tag(document, "div",
+                       vec![HtmlAttr { name: "class", value: "form-row" }],
+                       None, Some(rows))?;
+    let col = |class: &str| make_tag(document, "div",
+                                     vec![HtmlAttr { name: "class", value: class }],
+                                     None, Some(&row));
+
+    let item_select = make_tag(document, "select",
+                               vec![
+                                   HtmlAttr { name: "name", value: "item" },
+                                   HtmlAttr { name: "class", value: "custom-select" },
+                               ], None, Some(&col("form-group col-md-4")?))?;
+    for (kind, label) in items.iter() {
+        let value = format!("{}", *kind as i32);
+        let mut attr = vec![HtmlAttr { name: "value", value: &value }];
+        if change.map(|c| c.item) == Some(*kind) {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(label), Some(&item_select))?;
+    }
+
+    let renewal_group = make_tag(document, "div",
+                                 vec![HtmlAttr { name: "class", value: "input-group" }],
+                                 None, Some(&col("form-group col-md-3")?))?;
+    let renewal = format!("{}", change.map_or(1, |c| c.renewal));
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "number" },
+                 HtmlAttr { name: "name", value: "renewal" },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "min", value: "1" },
+                 HtmlAttr { name: "value", value: &renewal },
+             ], None, Some(&renewal_group))?;
+    let append = make_tag(document, "div",
+                          vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                          None, Some(&renewal_group))?;
+    make_tag(document, "span",
+             vec![HtmlAttr { name: "class", value: "input-group-text" }],
+             Some(catalog.schedule_renewal), Some(&append))?;
+
+    let (kind, value) = match change.map(|c| c.change) {
+        Some(Change::Percent(p)) => ("percent", format!("{}", p)),
+        Some(Change::Set(v)) => ("set", format!("{}", v)),
+        None => ("set", String::new()),
+    };
+    let kind_select = make_tag(document, "select",
+                               vec![
+                                   HtmlAttr { name: "name", value: "kind" },
+                                   HtmlAttr { name: "class", value: "custom-select" },
+                               ], None, Some(&col("form-group col-md-2")?))?;
+    for (option, label) in [("set", catalog.schedule_set), ("percent", catalog.schedule_percent)] {
+        let mut attr = vec![HtmlAttr { name: "value", value: option }];
+        if option == kind {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(label), Some(&kind_select))?;
+    }
+
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "text" },
+                 HtmlAttr { name: "name", value: "value" },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "inputmode", value: "decimal" },
+                 HtmlAttr { name: "value", value: &value },
+             ], None, Some(&col("form-group col-md-2")?))?;
+
+    make_remove_button(document, &row, rows, &col("form-group col-md-1")?)?;
+
+    Ok(row)
+}
+
+/// Names of the people sharing the property and rows of `split::SplitRule`, read back by `split::read_split`.
+pub struct SplitForm {
+    label: LabelForInputItem,
+    detail: DetailForInputItem,
+    items: Rc<Vec<(ItemKind, &'static str)>>,
+    initial: Split,
+    catalog: &'static Catalog,
+}
+
+impl SplitForm {
+    pub fn new(items: Vec<(ItemKind, &'static str)>, initial: Split, catalog: &'static Catalog) -> Self {
+        let id = InputId::SplitPeople.to_string();
+        let detail_id = HtmlId(format!("{}_detail", id));
+        SplitForm {
+            label: LabelForInputItem {
+                text: catalog.split_title.to_string(),
+                input_id: HtmlId(id),
+                detail_id: detail_id.clone(),
+            },
+            detail: DetailForInputItem {
+                id: detail_id,
+                text: catalog.split_detail.to_string(),
+            },
+            items: Rc::new(items),
+            initial,
+            catalog,
+        }
+    }
+}
+
+impl HtmlItem for SplitForm {
+    fn to_html(&self, document: &Document) -> Result<Vec<Element>, JsValue> {
+        let people = make_tag(document, "div",
+                              vec![HtmlAttr { name: "class", value: "form-group" }],
+                              None, None)?;
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "text" },
+                     HtmlAttr { name: "id", value: &InputId::SplitPeople.to_string() },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "placeholder", value: self.catalog.split_people },
+                     HtmlAttr { name: "aria-describedby", value: &format!("{}", &self.label.detail_id) },
+                     HtmlAttr { name: "value", value: &self.initial.people.join(", ") },
+                 ], None, Some(&people))?;
+
+        let rows = make_tag(document, "div",
+                            vec![HtmlAttr { name: "id", value: &InputId::SplitRows.to_string() }],
+                            None, None)?;
+        for rule in self.initial.rules.iter() {
+            make_split_row(document, &rows, &self.items, self.catalog, Some(rule))?;
+        }
+
+        let add_button = make_tag(document, "button",
+                                  vec![
+                                      HtmlAttr { name: "type", value: "button" },
+                                      HtmlAttr { name: "class", value: "btn btn-outline-secondary btn-sm mb-3" },
+                                  ], Some(self.catalog.split_add), None)?;
+        {
+            let document = document.clone();
+            let rows = rows.clone();
+            let items = self.items.clone();
+            let catalog = self.catalog;
+            let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+                if let Err(e) = make_split_row(&document, &rows, &items, catalog, None) {
+                    web_sys::console::error_1(&e);
+                }
+            }) as Box<dyn FnMut(_)>);
+            add_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+            closure.forget();
+        }
+
+        let label = self.label.to_html(document)?;
+        let detail = self.detail.to_html(document)?;
+        Ok(label
+            .into_iter()
+            .chain(detail)
+            .chain(vec![people, rows, add_button])
+            .collect())
+    }
+}
+
+fn make_split_row(document: &Document, rows: &Element, items: &[(ItemKind, &'static str)],
+                  catalog: &'static Catalog, rule: Option<&SplitRule>) -> Result<Element, JsValue> {
+    let row = make_tag(document, "div",
+                       vec![HtmlAttr { name: "class", value: "form-row" }],
+                       None, Some(rows))?;
+    let col = |class: &str| make_tag(document, "div",
+                                     vec![HtmlAttr { name: "class", value: class }],
+                                     None, Some(&row));
+
+    let item_select = make_tag(document, "select",
+                               vec![
+                                   HtmlAttr { name: "name", value: "item" },
+                                   HtmlAttr { name: "class", value: "custom-select" },
+                               ], None, Some(&col("form-group col-md-4")?))?;
+    for (kind, label) in items.iter() {
+        let value = format!("{}", *kind as i32);
+        let mut attr = vec![HtmlAttr { name: "value", value: &value }];
+        if rule.map(|r| r.item) == Some(*kind) {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(label), Some(&item_select))?;
+    }
+
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "text" },
+                 HtmlAttr { name: "name", value: "person" },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "placeholder", value: catalog.split_person },
+                 HtmlAttr { name: "value", value: rule.map_or("", |r| r.person.as_str()) },
+             ], None, Some(&col("form-group col-md-3")?))?;
+
+    let (kind, value) = match rule.map(|r| r.share) {
+        Some(Share::Percent(p)) => ("percent", format!("{}", p)),
+        Some(Share::Fixed(v)) => ("fixed", format!("{}", v)),
+        None => ("percent", String::new()),
+    };
+    let kind_select = make_tag(document, "select",
+                               vec![
+                                   HtmlAttr { name: "name", value: "kind" },
+                                   HtmlAttr { name: "class", value: "custom-select" },
+                               ], None, Some(&col("form-group col-md-2")?))?;
+    for (option, label) in [("percent", catalog.split_percent), ("fixed", catalog.split_fixed)] {
+        let mut attr = vec![HtmlAttr { name: "value", value: option }];
+        if option == kind {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(label), Some(&kind_select))?;
+    }
+
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "text" },
+                 HtmlAttr { name: "name", value: "value" },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "inputmode", value: "decimal" },
+                 HtmlAttr { name: "value", value: &value },
+             ], None, Some(&col("form-group col-md-2")?))?;
+
+    make_remove_button(document, &row, rows, &col("form-group col-md-1")?)?;
+
+    Ok(row)
+}
+
+/// A button removing `row` from `rows`.
+fn make_remove_button(document: &Document, row: &Element, rows: &Element, parent: &Element) -> Result<(), JsValue> {
+    let remove_button = make_tag(document, "button",
+                                 vec![
+                                     HtmlAttr { name: "type", value: "button" },
+                                     HtmlAttr { name: "class", value: "btn btn-outline-secondary" },
+                                 ], Some("×"), Some(parent))?;
+    let row = row.clone();
+    let rows = rows.clone();
+    let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+        row.remove();
+        // lets live mode notice the removal
+        let init = web_sys::EventInit::new();
+        init.set_bubbles(true);
+        let dispatched = web_sys::Event::new_with_event_init_dict("change", &init)
+            .and_then(|e| rows.dispatch_event(&e));
+        if let Err(e) = dispatched {
+            web_sys::console::error_1(&e);
+        }
+    }) as Box<dyn FnMut(_)>);
+    remove_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    Ok(())
+}
+
+/// A row of `components::Component`, read back by `components::read_components`.
+fn make_component_row(document: &Document, rows: &Element, units: &[(UnitId, &'static str)],
+                      interval_suffix: &'static str, charge: Option<&CalcRentItem>) -> Result<Element, JsValue> {
+    let row = make_tag(document, "div",
+                       vec![HtmlAttr { name: "class", value: "form-row" }],
+                       None, Some(rows))?;
+    let col = |class: &str| make_tag(document, "div",
+                                     vec![HtmlAttr { name: "class", value: class }],
+                                     None, Some(&row));
+
+    let value = charge.map_or(String::new(), |c| format!("{}", c.value));
+    make_tag(document, "input",
+             vec![
+                 HtmlAttr { name: "type", value: "text" },
+                 HtmlAttr { name: "name", value: "value" },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "inputmode", value: "decimal" },
+                 HtmlAttr { name: "value", value: &value },
+             ], None, Some(&col("form-group col-md-4")?))?;
+
+    let unit_select = make_tag(document, "select",
+                               vec![
+                                   HtmlAttr { name: "name", value: "unit" },
+                                   HtmlAttr { name: "class", value: "custom-select" },
+                               ], None, Some(&col("form-group col-md-4")?))?;
+    for (unit, label) in units.iter() {
+        let value = format!("{}", *unit as i32);
+        let mut attr = vec![HtmlAttr { name: "value", value: &value }];
+        if charge.map(|c| c.unit) == Some(*unit) {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(label), Some(&unit_select))?;
+    }
+
+    if units.iter().any(|(u, _)| *u == UnitId::EveryNMonthsYen) {
+        let interval_group = make_tag(document, "div",
+                                      vec![HtmlAttr { name: "class", value: "input-group" }],
+                                      None, Some(&col("form-group col-md-3")?))?;
+        let interval = format!("{}", charge.map_or(12, |c| c.interval));
+        make_tag(document, "input",
+                 vec![
+                     HtmlAttr { name: "type", value: "number" },
+                     HtmlAttr { name: "name", value: "interval" },
+                     HtmlAttr { name: "class", value: "form-control" },
+                     HtmlAttr { name: "min", value: "1" },
+                     HtmlAttr { name: "value", value: &interval },
+                 ], None, Some(&interval_group))?;
+        let append = make_tag(document, "div",
+                              vec![HtmlAttr { name: "class", value: "input-group-append" }],
+                              None, Some(&interval_group))?;
+        make_tag(document, "span",
+                 vec![HtmlAttr { name: "class", value: "input-group-text" }],
+                 Some(interval_suffix), Some(&append))?;
+        show_interval_on_select(&unit_select, interval_group.dyn_into::<web_sys::HtmlElement>()?)?;
+    }
+
+    make_remove_button(document, &row, rows, &col("form-group col-md-1")?)?;
+    Ok(row)
+}
+
+/// Shows `target` only while `UnitId::EveryNMonthsYen` is selected in `unit_select`.
+fn show_interval_on_select(unit_select: &Element, target: web_sys::HtmlElement) -> Result<(), JsValue> {
+    let selector = unit_select.clone().dyn_into::<web_sys::HtmlSelectElement>()?;
+    let update = move || {
+        let shown = selector.value() == format!("{}", UnitId::EveryNMonthsYen as i32);
+        if let Err(e) = target.style().set_property("display", if shown { "" } else { "none" }) {
+            web_sys::console::error_1(&e);
+        }
+    };
+    update();
+    let closure = Closure::wrap(Box::new(move |_: web_sys::Event| update()) as Box<dyn FnMut(_)>);
+    unit_select.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize, Serializer};
+use strum::IntoEnumIterator;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlInputElement};
+
+use crate::calculator::RentElements;
+use crate::cash_flow::item_flows;
+use crate::constants::ItemKind;
+
+/// Payments of deferred items (繰延資産) from this amount on are amortized instead of deducted when paid.
+pub const AMORTIZATION_THRESHOLD: i32 = 200_000;
+/// Longest amortization period in months.
+pub const AMORTIZATION_MONTHS: i32 = 60;
+
+/// Business share of an item, overriding `HomeOffice::percent`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct ItemShare {
+    pub item: ItemKind,
+    pub percent: f64,
+}
+
+/// Part of the costs used for business (家事按分), for freelancers working from home.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct HomeOffice {
+    /// Business share of every item and of the running costs, in percent.
+    pub percent: f64,
+    pub items: Vec<ItemShare>,
+}
+
+/// One cost item over the lease.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemExpense {
+    /// The number of the JS `ItemKind`.
+    #[serde(serialize_with = "item_number")]
+    pub item: ItemKind,
+    pub percent: f64,
+    pub paid: i32,
+    pub business: i32,
+    pub personal: i32,
+    /// Some payment is amortized as a deferred asset.
+    pub deferred: bool,
+}
+
+fn item_number<S: Serializer>(item: &ItemKind, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_i32(*item as i32)
+}
+
+/// Costs of one tax year, or of one year of the lease without the move-in date.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpenseYear {
+    pub year: i32,
+    pub paid: i32,
+    /// Business part of what was paid in the year.
+    pub business: i32,
+    pub personal: i32,
+    /// Business expenses of the year: what was paid, except deferred payments, plus their amortization.
+    pub deductible: i32,
+    /// Part of `deductible` amortizing deferred payments.
+    pub amortized: i32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HomeOfficeReport {
+    /// The years are calendar years. Otherwise they count the years of the lease from 1.
+    pub calendar: bool,
+    pub items: Vec<ItemExpense>,
+    /// The running costs not in the rent, at `HomeOffice::percent`.
+    pub utilities: i32,
+    pub utilities_business: i32,
+    pub years: Vec<ExpenseYear>,
+}
+
+impl HomeOffice {
+    pub fn is_empty(&self) -> bool {
+        self.percent <= 0.0 && self.items.iter().all(|i| i.percent <= 0.0)
+    }
+
+    pub fn percent_of(&self, item: ItemKind) -> f64 {
+        self.items.iter().rev().find(|i| i.item == item).map_or(self.percent, |i| i.percent)
+    }
+}
+
+/// Items amortized when paid in large amounts: the key money and the renewal fees.
+fn deferrable(item: ItemKind) -> bool {
+    matches!(item, ItemKind::KeyMoney | ItemKind::ContractUpdateFee)
+}
+
+fn business_part(amount: i64, percent: f64) -> i64 {
+    (amount as f64 * percent / 100.0).round() as i64
+}
+
+/// Splits the costs of `e` into business and personal parts, by year. `None` without a business share.
+/// Deferred payments are amortized monthly over the contract period, or 5 years if longer,
+/// and what is left when the lease ends is deducted then.
+pub fn home_office_report(e: &RentElements) -> Option<HomeOfficeReport> {
+    if e.home_office.is_empty() {
+        return None;
+    }
+    let months = e.lease_months();
+    let stay = e.stay();
+    // year of the `m`-th month; the last entry of the flows is at move-out
+    let year_of = |m: i32| match stay {
+        Some(s) if m >= months => s.move_out.year(),
+        Some(s) => s.move_in.add_months(m).year(),
+        None => m.min(months - 1) / 12 + 1,
+    };
+    let period = e.contract_period.get(0, 0).clamp(1, AMORTIZATION_MONTHS);
+
+    let mut years: BTreeMap<i32, ExpenseYear> = BTreeMap::new();
+    let mut add = |m: i32, paid: i64, business: i64, deductible: i64, amortized: i64| {
+        let y = years.entry(year_of(m)).or_default();
+        y.paid += paid as i32;
+        y.business += business as i32;
+        y.deductible += deductible as i32;
+        y.amortized += amortized as i32;
+    };
+
+    let mut items = vec![];
+    for kind in e.cost_items() {
+        let flows = item_flows(e, kind);
+        if flows.iter().all(|f| *f == 0) {
+            continue;
+        }
+        let percent = e.home_office.percent_of(kind);
+        let mut expense = ItemExpense { item: kind, percent, paid: 0, business: 0, personal: 0, deferred: false };
+        for (m, flow) in flows.iter().enumerate().filter(|(_, f)| **f != 0) {
+            let m = m as i32;
+            let business = business_part(*flow, percent);
+            expense.paid += *flow as i32;
+            expense.business += business as i32;
+            if deferrable(kind) && *flow >= AMORTIZATION_THRESHOLD as i64 {
+                expense.deferred = true;
+                add(m, *flow, business, 0, 0);
+                let end = (m + period).min(months);
+                for k in m..end {
+                    let done = |k: i32| business * (k - m) as i64 / period as i64;
+                    // the rest is deducted in the last month of the lease
+                    let part = if k == end - 1 { business - done(k) } else { done(k + 1) - done(k) };
+                    add(k, 0, 0, part, part);
+                }
+            } else {
+                add(m, *flow, business, business, 0);
+            }
+        }
+        expense.personal = expense.paid - expense.business;
+        items.push(expense);
+    }
+
+    let monthly = e.running.monthly();
+    let mut utilities = 0;
+    let mut utilities_business = 0;
+    for m in 0..months {
+        let business = business_part(monthly as i64, e.home_office.percent);
+        add(m, monthly as i64, business, business, 0);
+        utilities += monthly;
+        utilities_business += business as i32;
+    }
+
+    Some(HomeOfficeReport {
+        calendar: stay.is_some(),
+        items,
+        utilities,
+        utilities_business,
+        years: years
+            .into_iter()
+            .map(|(year, y)| ExpenseYear { year, personal: y.paid - y.business, ..y })
+            .collect(),
+    })
+}
+
+/// Input of the business share of `kind`, left empty for the common one.
+pub fn item_percent_id(kind: ItemKind) -> String {
+    format!("{}_business", kind.input_id())
+}
+
+/// Reads the business shares of single items, skipping the empty ones.
+pub fn read_item_percents(document: &Document) -> Vec<ItemShare> {
+    ItemKind::iter()
+        .filter_map(|item| {
+            let value = document
+                .get_element_by_id(&item_percent_id(item))?
+                .dyn_into::<HtmlInputElement>()
+                .ok()?
+                .value();
+            let percent = value.trim().parse::<f64>().ok()?;
+            Some(ItemShare { item, percent })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentElements, RentItem};
+    use crate::constants::{ItemKind, RunningKind, UnitId};
+    use crate::date::Date;
+    use crate::home_office::{HomeOffice, ItemExpense, ItemShare, home_office_report};
+
+    #[test]
+    fn business_share() {
+        assert_eq!(home_office_report(&RentElements::default()), None);
+
+        let mut e = RentElements {
+            home_office: HomeOffice {
+                percent: 30.0,
+                items: vec![ItemShare { item: ItemKind::KeyMoney, percent: 50.0 }],
+            },
+            ..Default::default()
+        };
+        e.running.set(RunningKind::Electricity, 10000, false);
+        let r = home_office_report(&e).unwrap();
+        assert!(!r.calendar);
+        let rent = r.items.iter().find(|i| i.item == ItemKind::Rent).unwrap();
+        assert_eq!(rent.paid, 50000 * 24);
+        assert_eq!(rent.business, 15000 * 24);
+        assert_eq!(rent.personal, 35000 * 24);
+        let key_money = r.items.iter().find(|i| i.item == ItemKind::KeyMoney).unwrap();
+        assert_eq!(key_money.business, 25000);
+        assert!(!key_money.deferred);
+        assert_eq!(r.utilities_business, 3000 * 24);
+        assert_eq!(r.years.len(), 2);
+        assert_eq!(r.years.iter().map(|y| y.paid).sum::<i32>(), r.items.iter().map(|i| i.paid).sum::<i32>() + r.utilities);
+        assert_eq!(r.years[0].deductible, r.years[0].business);
+    }
+
+    #[test]
+    fn amortized_key_money() {
+        let e = RentElements {
+            key_money: RentItem::new(240000, UnitId::OneShotYen),
+            lease_period: RentItem::new(36, UnitId::Month),
+            move_in: Date::new(2024, 4, 1),
+            home_office: HomeOffice { percent: 50.0, items: vec![] },
+            ..Default::default()
+        };
+        let r = home_office_report(&e).unwrap();
+        assert!(r.calendar);
+        assert!(r.items.iter().find(|i| i.item == ItemKind::KeyMoney).unwrap().deferred);
+        // 120,000 over the 24 months of the contract, 9 of them in 2024
+        let years: Vec<i32> = r.years.iter().map(|y| y.year).collect();
+        assert_eq!(years, vec![2024, 2025, 2026, 2027]);
+        assert_eq!(r.years[0].amortized, 45000);
+        assert_eq!(r.years[1].amortized, 60000);
+        assert_eq!(r.years[2].amortized, 15000);
+        assert_eq!(r.years[0].deductible, r.years[0].business - 120000 + 45000);
+        assert_eq!(r.years.iter().map(|y| y.deductible).sum::<i32>(), r.years.iter().map(|y| y.business).sum::<i32>());
+    }
+
+    #[test]
+    fn item_as_js_number() {
+        let expense = ItemExpense {
+            item: ItemKind::Deposit, percent: 0.0, paid: 0, business: 0, personal: 0, deferred: false,
+        };
+        let json = serde_json::to_value(expense).unwrap();
+        assert_eq!(json["item"], 18);
+        assert_eq!(json["item"], ItemKind::Deposit as i32);
+    }
+}
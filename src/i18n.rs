@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::calculator::RentResult;
+use crate::constants::{ContractType, ItemKind, MovingKind, RunningKind, UnitId};
+
+mod en;
+mod ja;
+
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Lang {
+    Ja,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Ja, Lang::En];
+
+    /// Picks the language from `navigator.language`, falling back to Japanese.
+    pub fn detect() -> Lang {
+        web_sys::window()
+            .and_then(|w| w.navigator().language())
+            .map(|l| if l.starts_with("ja") { Lang::Ja } else { Lang::En })
+            .unwrap_or(Lang::Ja)
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Ja => "ja",
+            Lang::En => "en",
+        }
+    }
+
+    /// Name of the language written in the language itself.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lang::Ja => "日本語",
+            Lang::En => "English",
+        }
+    }
+
+    pub fn catalog(self) -> &'static Catalog {
+        match self {
+            Lang::Ja => &ja::CATALOG,
+            Lang::En => &en::CATALOG,
+        }
+    }
+}
+
+/// Escapes text typed by the user, such as names, for the result written as HTML.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Every user-visible string of the UI.
+pub struct Catalog {
+    pub title: &'static str,
+    pub calc_button: &'static str,
+    pub live_switch: &'static str,
+    pub result_header: &'static str,
+    pub result_title_placeholder: &'static str,
+    pub result_body_placeholder: &'static str,
+    pub pending_title: &'static str,
+    pub pending_body: &'static str,
+    pub export_label: &'static str,
+    pub import_label: &'static str,
+    pub import_placeholder: &'static str,
+    pub restore_button: &'static str,
+    pub back_to_top: &'static str,
+    pub made_by: &'static str,
+    /// Shown when a money field cannot be read as yen.
+    pub yen_invalid: &'static str,
+    pub listing_label: &'static str,
+    pub listing_placeholder: &'static str,
+    pub listing_button: &'static str,
+    /// Names of the recognized items and of the items left unchanged.
+    pub listing_report: fn(&[&str], &[&str]) -> String,
+    pub schedule_title: &'static str,
+    pub schedule_detail: &'static str,
+    /// Follows the renewal number, e.g. `2` + `回目の更新から`.
+    pub schedule_renewal: &'static str,
+    pub schedule_set: &'static str,
+    pub schedule_percent: &'static str,
+    pub schedule_add: &'static str,
+    pub component_add: &'static str,
+    /// Follows the N of the every-N-months unit.
+    pub interval_suffix: &'static str,
+    pub free_rent_title: &'static str,
+    pub free_rent_detail: &'static str,
+    /// Follows the month the free rent starts from.
+    pub free_rent_start: &'static str,
+    /// Follows the minimum stay in months.
+    pub free_rent_min_stay: &'static str,
+    pub free_rent_covers_management_fee: &'static str,
+    pub contract_type_label: &'static str,
+    pub contract_type_detail: &'static str,
+    pub contract_type_name: fn(ContractType) -> &'static str,
+    /// Label and detailed description of the date.
+    pub move_in: (&'static str, &'static str),
+    pub move_out: (&'static str, &'static str),
+    pub discount_rate: (&'static str, &'static str),
+    pub area_title: &'static str,
+    pub area_detail: &'static str,
+    /// Suffixes of the area and occupant inputs.
+    pub square_meters: &'static str,
+    pub jo: &'static str,
+    pub occupants: &'static str,
+    pub income_title: &'static str,
+    pub income_detail: &'static str,
+    /// Suffixes of the income inputs.
+    pub annual_income: &'static str,
+    pub take_home_pay: &'static str,
+    pub budget_percent: &'static str,
+    pub subsidy_title: &'static str,
+    pub subsidy_detail: &'static str,
+    /// Suffixes of the subsidy inputs.
+    pub housing_allowance: &'static str,
+    pub subsidy_rent_limit: &'static str,
+    pub employer_rent_percent: &'static str,
+    pub employer_rent_cap: &'static str,
+    pub employer_paid_items: &'static str,
+    pub home_office_title: &'static str,
+    pub home_office_detail: &'static str,
+    pub home_office_percent: &'static str,
+    /// Placeholder of the items' own shares.
+    pub home_office_common: &'static str,
+    pub purchase_title: &'static str,
+    pub purchase_detail: &'static str,
+    /// Suffixes of the purchase inputs.
+    pub purchase_price: &'static str,
+    pub down_payment: &'static str,
+    pub closing_costs: &'static str,
+    pub resale_value: &'static str,
+    pub mortgage_rate: &'static str,
+    pub mortgage_years: &'static str,
+    pub owner_management_fee: &'static str,
+    pub repair_reserve: &'static str,
+    pub property_tax: &'static str,
+    pub purchase_horizon: &'static str,
+    pub split_title: &'static str,
+    pub split_detail: &'static str,
+    /// Placeholders of the names.
+    pub split_people: &'static str,
+    pub split_person: &'static str,
+    pub split_percent: &'static str,
+    pub split_fixed: &'static str,
+    pub split_add: &'static str,
+    pub current_home_title: &'static str,
+    pub current_home_detail: &'static str,
+    /// Follows the monthly rent of the current apartment.
+    pub current_rent: &'static str,
+    /// Follows the notice period in months.
+    pub current_notice: &'static str,
+    pub current_move_out: &'static str,
+    pub overlap_in_total: &'static str,
+    pub moving_title: &'static str,
+    pub moving_detail: &'static str,
+    /// Follows the amount of the moving cost.
+    pub moving_kind_name: fn(MovingKind) -> &'static str,
+    pub moving_presets: &'static str,
+    pub moving_in_total: &'static str,
+    pub running_title: &'static str,
+    pub running_detail: &'static str,
+    /// Follows the monthly estimate.
+    pub running_kind_name: fn(RunningKind) -> &'static str,
+    pub running_included: &'static str,
+    pub running_presets: &'static str,
+    pub renewal_title: &'static str,
+    pub renewal_detail: &'static str,
+    /// Suffixes of the renewal inputs.
+    pub renewal_months_left: &'static str,
+    pub renewal_fee: &'static str,
+    pub renewed_rent: &'static str,
+    pub renewal_contract_period: &'static str,
+    pub renewal_horizon: &'static str,
+    /// Label and detailed description of the item.
+    pub item_text: fn(ItemKind) -> (&'static str, &'static str),
+    pub unit_label: fn(UnitId) -> &'static str,
+    pub result_title: fn(&RentResult) -> String,
+    pub result_body: fn(&RentResult) -> String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentCalculator, RentElements};
+    use crate::i18n::{Lang, escape_html};
+    use crate::split::Split;
+
+    #[test]
+    fn names_are_escaped() {
+        assert_eq!(escape_html(r#"<a href="x">T&'s</a>"#), "&lt;a href=&quot;x&quot;&gt;T&amp;&#39;s&lt;/a&gt;");
+
+        let name = "<img src=x onerror=alert(1)>";
+        let e = RentElements {
+            split: Split { people: vec![name.to_string(), "B".to_string()], rules: vec![] },
+            ..Default::default()
+        };
+        let result = RentCalculator {}.calc(e).unwrap();
+        for lang in Lang::ALL.iter() {
+            let body = (lang.catalog().result_body)(&result);
+            assert!(!body.contains("<img"));
+            assert!(body.contains("&lt;img src=x onerror=alert(1)&gt;"));
+        }
+    }
+}
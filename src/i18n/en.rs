@@ -0,0 +1,462 @@
+use crate::calculator::RentResult;
+use crate::constants::{ContractType, ItemKind, MovingKind, RunningKind, UnitId};
+use crate::decision::{Choice, Home};
+use crate::purchase::Tenure;
+use crate::i18n::{Catalog, escape_html};
+use crate::yen::format_yen;
+
+pub const CATALOG: Catalog = Catalog {
+    title: "True Rent Calculator",
+    calc_button: "Calculate",
+    live_switch: "Recalculate as I type",
+    result_header: "Result",
+    result_title_placeholder: "The result will appear here",
+    result_body_placeholder: "Details of the result",
+    pending_title: "Waiting for input",
+    pending_body: "The result will appear once all fields are filled in",
+    export_label: "Export (copy this to save your input)",
+    import_label: "Import (paste saved input here and press the restore button)",
+    import_placeholder: "Under development",
+    restore_button: "Restore",
+    back_to_top: "Back to top",
+    made_by: "made with ♥ by esplo: ",
+    yen_invalid: "Cannot read this as a yen amount (e.g. 52000, 52,000, 5.2万)",
+    listing_label: "Fill from listing (paste the cost section of a listing page)",
+    listing_placeholder: "賃料 8.5万円 管理費 5,000円 敷金1ヶ月 礼金1ヶ月 保証会社利用必須 初回50%",
+    listing_button: "Read",
+    listing_report,
+    schedule_title: "Changes at renewal",
+    schedule_detail: "Add rent increases at renewal, or guarantee renewal fees that differ from the initial one. A change stays in force for the following terms.",
+    schedule_renewal: "th renewal onward",
+    schedule_set: "becomes (yen)",
+    schedule_percent: "changes by (%)",
+    schedule_add: "Add change",
+    component_add: "+ Add component (e.g. an initial and a yearly part)",
+    interval_suffix: "months each",
+    free_rent_title: "Free rent conditions",
+    free_rent_detail: "Free months usually come right after moving in, and the management fee is usually still charged. Many contracts require paying the free rent back when moving out before a minimum stay, often 1 or 2 years. Enter 0 if there is no minimum stay.",
+    free_rent_start: "th month onward",
+    free_rent_min_stay: "months minimum, or paid back",
+    free_rent_covers_management_fee: "Management fee is free too",
+    contract_type_label: "Contract type",
+    contract_type_detail: "An ordinary lease (普通借家) can be renewed by paying the renewal fee. A fixed-term lease (定期借家) ends at the end of the term and continues only with a re-contract and its fee. Some fixed-term leases cannot be re-contracted at all.",
+    contract_type_name,
+    move_in: (
+        "Move-in date (optional)",
+        "Prorates the rent and management fee of the move-in month by the day. With a move-out date as well, the planned stay is taken from the two dates.",
+    ),
+    move_out: (
+        "Move-out date (optional)",
+        "Prorates the rent and management fee of the move-out month by the day. Without a move-in date, the move-in date is counted back from the planned stay.",
+    ),
+    area_title: "Size and occupants (optional)",
+    area_detail: "Properties of different sizes are hard to compare by effective rent alone. Enter the floor area (専有面積, ㎡), the tatami mats (畳) of the rooms and the number of occupants to get the effective rent per ㎡, per 畳 and per person. 0 if unknown.",
+    square_meters: "㎡",
+    jo: "畳 (jo)",
+    occupants: "occupants",
+    income_title: "Income (optional)",
+    income_detail: "Landlords and guarantee companies usually want the rent and management fee to be at most 1/3 of the monthly income, that is an annual income of 36 times the rent. Enter the annual gross income, the monthly take-home pay and the share of the gross income you allow for rent to see the effective rent and cost of living against the income, whether the rent likely passes screening, and the highest rent within the budget. An annual income of 0 turns this off.",
+    annual_income: "yen/year gross",
+    take_home_pay: "yen/month take-home",
+    budget_percent: "% at most",
+    subsidy_title: "Housing allowance and corporate lease (optional)",
+    subsidy_detail: "What the employer pays: a fixed monthly housing allowance, or for a corporate lease a share of the rent, capped a month, and the items the company pays in full, such as the initial fees. Subsidies are often only paid while the rent and management fee stay under a limit, which can be entered too. The total stays as it is, and what you pay yourself is worked out next to it.",
+    housing_allowance: "yen/month allowance",
+    subsidy_rent_limit: "yen/month rent limit (0 for none)",
+    employer_rent_percent: "% of the rent paid by the employer",
+    employer_rent_cap: "yen/month at most (0 for no cap)",
+    employer_paid_items: "Items the employer pays in full",
+    home_office_title: "Home office (optional)",
+    home_office_detail: "The business share of the costs for freelancers working from home (家事按分). The common share applies to every item and to the running costs, unless an item has its own. Key money and renewal fees of 200,000 yen or more are deferred assets (繰延資産), amortized monthly over the contract period, 5 years at most, with what is left deducted at move-out. Years are calendar years when the move-in date is given, and years of the lease otherwise.",
+    home_office_percent: "% for business",
+    home_office_common: "common share",
+    purchase_title: "Compare with buying (optional)",
+    purchase_detail: "Compares renting the property over a number of years, renewals included, with buying a home. Enter the price, the down payment, the mortgage rate (compounded monthly) and term, the management fee, the repair reserve, the property tax, the closing costs and the expected sale price at the end, after selling costs; the value is assumed to decline linearly to it. The cumulative cost of buying at the end of each year is what was paid, less the sale price and plus the loan left. A comparison over 0 years turns this off.",
+    purchase_price: "yen price",
+    down_payment: "yen down payment",
+    closing_costs: "yen closing costs",
+    resale_value: "yen resale value",
+    mortgage_rate: "% mortgage rate",
+    mortgage_years: "year mortgage",
+    owner_management_fee: "yen/month management fee",
+    repair_reserve: "yen/month repair reserve",
+    property_tax: "yen/year property tax",
+    purchase_horizon: "years compared",
+    split_title: "Cost splitting (optional)",
+    split_detail: "When two or three people share, the rent is often split by room size, or one person pays the parking. Enter the names separated by commas to get each person's upfront payment, monthly payment and effective rent. Items without a rule are split equally. A fixed amount is per charge (every month for the rent); the rest follows the percentages, or is split equally without any.",
+    split_people: "e.g. Aki, Ben",
+    split_person: "Name",
+    split_percent: "%",
+    split_fixed: "yen",
+    split_add: "Add a rule",
+    current_home_title: "Current apartment (optional)",
+    current_home_detail: "Leaving needs notice (解約予告), usually a month, so both rents are paid from the start of the new lease until the old one ends. Enter the rent plus management fee, the notice period and the move-out date if already planned. Without a date, notice is assumed to be given when the new lease starts. Leave the rent at 0 if you are not moving out of anywhere.",
+    current_rent: "yen/month",
+    current_notice: "months' notice",
+    current_move_out: "Move-out date of the current apartment",
+    overlap_in_total: "Include in the new total",
+    moving_title: "Moving costs (optional)",
+    moving_detail: "Besides what the property charges, moving in costs the movers, curtains and lighting (often not included in Japanese rentals), air conditioner installation, internet setup and disposal of old furniture. \"Fill in typical amounts\" fills the empty fields with typical amounts for a single person. When included, they are spread over the stay in the monthly figure.",
+    moving_kind_name,
+    moving_presets: "Fill in typical amounts",
+    moving_in_total: "Include in the total and monthly cost",
+    running_title: "Utilities and internet (optional)",
+    running_detail: "Equal rents can mean very different monthly bills: free internet or not, flat-rate water (水道定額), city gas or propane (often twice as expensive), or all-electric. Enter monthly estimates and tick what the rent or management fee already covers. \"Fill in typical amounts\" fills the empty fields for a single person with city gas.",
+    running_kind_name,
+    running_included: "Included in the rent",
+    running_presets: "Fill in typical amounts",
+    renewal_title: "Renew or move",
+    renewal_detail: "When the current apartment is up for renewal, compares renewing with moving to the property above when the contract ends, and finds the cheapest way to renew or move at every later renewal. The current rent is taken from \"Current apartment\". Enter the months until the renewal, the renewal fee, the rent after renewing, the contract period, and the months to compare over. The moving costs are taken from \"Moving costs\" and paid at every move. A period of 0 turns the comparison off.",
+    renewal_months_left: "months to renewal",
+    renewal_fee: "yen renewal fee",
+    renewed_rent: "yen/month after",
+    renewal_contract_period: "month contract",
+    renewal_horizon: "months compared",
+    discount_rate: (
+        "Discount rate (% a year)",
+        "¥10,000 paid today weighs more than ¥10,000 paid later. Enter e.g. the return you would get on savings to see the present value of all payments and the level monthly payment worth the same, including the returns lost on the deposit. 0 turns this off.",
+    ),
+    item_text,
+    unit_label,
+    result_title,
+    result_body,
+};
+
+fn item_text(kind: ItemKind) -> (&'static str, &'static str) {
+    match kind {
+        ItemKind::Rent => (
+            "Rent (賃料)",
+            "The base monthly rent. If this were the only charge, this tool would not be needed.",
+        ),
+        ItemKind::ManagementFee => (
+            "Management fee (共益費・管理費)",
+            "The second standard monthly charge, usually advertised next to the rent. Fees quoted as \"N months of rent\" usually exclude it.",
+        ),
+        ItemKind::Deposit => (
+            "Deposit (敷金)",
+            "Paid back on moving out, minus restoration costs. Not counted as a cost, but the money is locked up meanwhile; with a discount rate, its opportunity cost is included.",
+        ),
+        ItemKind::KeyMoney => (
+            "Key money (礼金)",
+            "A major initial fee, typically one month of rent, paid to the owner as a \"thank you\". It is the first thing to be discounted when a unit is hard to fill.",
+        ),
+        ItemKind::BrokerageFee => (
+            "Brokerage fee (仲介手数料)",
+            "Another major initial fee, typically one month of rent, paid to the real estate agent. It can be zero when the owner pays the agent enough.",
+        ),
+        ItemKind::FreeRentPeriod => (
+            "Free rent (フリーレント)",
+            "Months with no rent. The only value you want to be large. The management fee is usually still charged, and moving out early may incur a penalty.",
+        ),
+        ItemKind::GuaranteeFee => (
+            "Guarantee fee (保証料)",
+            "Charged when a rent guarantee company is mandatory. Common patterns are 50% of the rent up front or 1% of the monthly total. A smaller fee is often charged again at renewal.",
+        ),
+        ItemKind::SupportFee => (
+            "24-hour support (24時間サポート)",
+            "Often mandatory in large buildings, though rarely used. If it is optional, think before signing up.",
+        ),
+        ItemKind::AssociationMembershipFee => (
+            "Neighborhood association fee (町内会費)",
+            "A contribution to the local neighborhood association. Unusual for rentals.",
+        ),
+        ItemKind::GuaranteeAdministrativeFee => (
+            "Guarantee administration fee (保証委託契約時事務手数料)",
+            "A one-time fee for the guarantee company's paperwork. Often surprisingly high and not negotiable.",
+        ),
+        ItemKind::InsuranceFee => (
+            "Insurance (保険料)",
+            "Fire insurance and the like. There is usually little choice of company or plan.",
+        ),
+        ItemKind::BicycleSpaceFee => (
+            "Bicycle / motorcycle parking (自転車・バイク置き場使用料)",
+            "Needed only if you own one. Usually renewed together with the lease.",
+        ),
+        ItemKind::CarSpaceFee => (
+            "Car parking (自動車置き場費用)",
+            "Usually optional. Spaces are scarce and can cost far more than bicycle parking in central Tokyo.",
+        ),
+        ItemKind::KeyChangeFee => (
+            "Key replacement (鍵交換費用)",
+            "A mandatory fee for replacing the lock on move-in, common except in new buildings.",
+        ),
+        ItemKind::CleaningFee => (
+            "Move-out cleaning (退去時清掃費用)",
+            "A fixed cleaning fee charged separately from the deposit, usually written in the special terms of the contract.",
+        ),
+        ItemKind::ContractUpdateFee => (
+            "Renewal fee (更新料)",
+            "Charged every time the lease is renewed, typically one month of rent. Common in the Kanto region.",
+        ),
+        ItemKind::RecontractFee => (
+            "Re-contract fee (再契約料)",
+            "Charged when a fixed-term lease is signed again to stay past its term. Used instead of the renewal fee only for fixed-term leases.",
+        ),
+        ItemKind::ContractPeriod => (
+            "Contract period",
+            "Months until the lease must be renewed. Usually 2 years, but varies for fixed-term leases.",
+        ),
+        ItemKind::LeasePeriod => (
+            "Planned stay",
+            "How many months you plan to live there. Longer stays spread initial fees such as key money over more months. Short stays such as monthly mansions can be given in days (30 days count as a month).",
+        ),
+    }
+}
+
+fn listing_report(recognized: &[&str], defaulted: &[&str]) -> String {
+    if recognized.is_empty() {
+        return "No items could be read".to_string();
+    }
+    format!("Read: {}. Unchanged: {}", recognized.join(", "), defaulted.join(", "))
+}
+
+fn unit_label(unit: UnitId) -> &'static str {
+    match unit {
+        UnitId::MonthlyYen => "yen / month",
+        UnitId::OneShotYen => "yen (once)",
+        UnitId::EveryContractYen => "yen / contract",
+        UnitId::EveryContractUpdateYen => "yen / renewal",
+        UnitId::UpdateRentPercent => "% of new rent / renewal",
+        UnitId::DailyYen => "yen / day",
+        UnitId::WeeklyYen => "yen / week",
+        UnitId::YearlyYen => "yen / year",
+        UnitId::EveryNMonthsYen => "yen / N months",
+        UnitId::Month => "months",
+        UnitId::Day => "days",
+    }
+}
+
+fn contract_type_name(contract_type: ContractType) -> &'static str {
+    match contract_type {
+        ContractType::Ordinary => "Ordinary lease",
+        ContractType::FixedTerm => "Fixed-term lease (re-contract possible)",
+        ContractType::FixedTermNoRecontract => "Fixed-term lease (no re-contract)",
+    }
+}
+
+fn result_title(result: &RentResult) -> String {
+    if result.counted_in_days {
+        return format!("Effective rent: {} yen/day ({} yen per 30 days)", result.daily, result.monthly)
+            + &living_cost(result);
+    }
+    format!("Effective rent: {} yen/month", result.monthly) + &living_cost(result)
+}
+
+fn living_cost(result: &RentResult) -> String {
+    if result.running_monthly == 0 {
+        return String::new();
+    }
+    let per = if result.counted_in_days { "per 30 days" } else { "/month" };
+    format!(" / Cost of living here: {} yen{}", result.living_monthly, per)
+}
+
+fn result_body(result: &RentResult) -> String {
+    let mut body = format!("Contract type: {}. ", contract_type_name(result.contract_type));
+    if result.exceeds_term {
+        body += "<strong>This fixed-term lease cannot be re-contracted, so you cannot stay beyond the contract period.</strong> ";
+    }
+    if result.counted_in_days {
+        body += &format!(
+            "Staying for {} days costs {} yen/day ({} yen per 30 days) on average, {} yen in total. \
+            Rent and management fee alone would be {} yen per 30 days, {} yen in total.",
+            result.lease_days, result.daily, result.monthly, result.total,
+            result.original_monthly, result.original_total
+        );
+    } else {
+        body += &format!(
+            "Living there for {} months costs {} yen/month on average, {} yen in total. \
+            Rent and management fee alone would be {} yen/month, {} yen in total. \
+            The effective rent is {} yen/month higher.",
+            result.lease_period, result.monthly, result.total, result.original_monthly, result.original_total,
+            result.monthly - result.original_monthly
+        );
+    }
+    if let (Some(move_in), Some(move_out), Some(first), Some(last), Some(payment)) =
+        (result.move_in, result.move_out, result.first_month, result.last_month, result.move_in_payment) {
+        body += &format!(
+            "<br>{} to {}: the move-in month is prorated to {} yen and the move-out month to {} yen (rent + management fee). \
+            If the next month is also due up front, {} yen is paid at move-in.",
+            move_in, move_out, format_yen(first), format_yen(last),
+            format_yen(payment)
+        );
+    }
+    if result.free_rent_clawed_back {
+        body += "<br><strong>You move out before the minimum stay, so the free rent has to be paid back.</strong>";
+    } else if result.free_rent_discount > 0 {
+        body += &format!("<br>Free rent saves {} yen.", format_yen(result.free_rent_discount));
+    }
+    if result.overlap_cost > 0 {
+        body += &format!(
+            "<br>The rents overlap for {} days, costing {} yen for the current apartment ({}).",
+            result.overlap_days, format_yen(result.overlap_cost),
+            if result.overlap_in_total { "included in the total" } else { "a moving cost, not in the total" }
+        );
+    }
+    if let Some(date) = result.notice_by {
+        body += &format!("<br>Give notice to the current apartment by {}.", date);
+    }
+    let mut per = vec![];
+    if let Some(v) = result.per_square_meter {
+        per.push(format!("{} yen per ㎡", format_yen(v)));
+    }
+    if let Some(v) = result.per_jo {
+        per.push(format!("{} yen per 畳", format_yen(v)));
+    }
+    if result.occupants > 1 {
+        per.push(format!("{} yen per person", format_yen(result.per_person)));
+    }
+    if !per.is_empty() {
+        body += &format!("<br>The effective rent is {}.", per.join(", "));
+    }
+    if let Some(s) = &result.subsidy {
+        if s.eligible {
+            body += &format!(
+                "<br>Without the {} yen the employer pays, you pay {} yen in total, an effective rent of <strong>{} yen/month</strong>.",
+                format_yen(s.employer_total), format_yen(s.net_total), format_yen(s.net_monthly)
+            );
+        } else {
+            body += &format!(
+                "<br><strong>The rent and management fee exceed the limit of {} yen/month, so the employer pays nothing.</strong>",
+                format_yen(s.rent_limit)
+            );
+        }
+    }
+    if let Some(h) = &result.home_office {
+        let deferred = h.items.iter().filter(|i| i.deferred).count();
+        body += &format!(
+            "<br>The business part is {} yen in total{}.",
+            format_yen(h.items.iter().map(|i| i.business).sum::<i32>() + h.utilities_business),
+            if deferred > 0 { ", deferred assets included" } else { "" }
+        );
+        for y in &h.years {
+            body += &format!(
+                "<br>{}: {} yen paid, {} yen for business, {} yen deductible",
+                if h.calendar { format!("{}", y.year) } else { format!("Year {}", y.year) },
+                format_yen(y.paid), format_yen(y.business), format_yen(y.deductible)
+            );
+            if y.amortized > 0 {
+                body += &format!(" ({} yen amortized)", format_yen(y.amortized));
+            }
+        }
+    }
+    if let Some(a) = &result.affordability {
+        body += &format!("<br>The effective rent is {:.1}% of the gross monthly income", a.rent_of_gross);
+        if let Some(t) = a.rent_of_take_home {
+            body += &format!(" ({:.1}% of the take-home pay)", t);
+        }
+        body += &format!(", the cost of living {:.1}%", a.living_of_gross);
+        if let Some(t) = a.living_of_take_home {
+            body += &format!(" ({:.1}%)", t);
+        }
+        body += &format!(
+            ".<br>{} (screening usually allows up to {} yen of rent and management fee). \
+            Keeping to {:.1}% of the gross income allows an effective rent of up to {} yen/month.",
+            if a.passes_screening { "The rent likely passes income screening" } else { "<strong>The rent may fail income screening</strong>" },
+            format_yen(a.screening_max_rent), a.budget_percent, format_yen(a.max_rent)
+        );
+    }
+    for s in &result.shares {
+        body += &format!(
+            "<br>{}: {} yen upfront, {} yen a month, effective rent {} yen/month.",
+            escape_html(&s.name), format_yen(s.upfront), format_yen(s.monthly), format_yen(s.effective)
+        );
+    }
+    if result.running_monthly > 0 {
+        body += &format!(
+            "<br>Utilities and internet add {} yen a month, for a cost of living of {} yen over the stay.",
+            format_yen(result.running_monthly), format_yen(result.living_total)
+        );
+    }
+    if result.moving_total > 0 {
+        let items: Vec<String> = result.moving_costs.iter()
+            .map(|i| format!("{} {}", format_yen(i.value), moving_kind_name(i.kind)))
+            .collect();
+        body += &format!(
+            "<br>Moving costs: {} yen ({}), {}.",
+            format_yen(result.moving_total), items.join(", "),
+            if result.moving_in_total { "included in the total" } else { "not in the total" }
+        );
+    }
+    if let Some(d) = &result.decision {
+        body += &format!(
+            "<br>Over {} months, renewing costs {} yen and moving costs {} yen: <strong>{} saves {} yen</strong>.",
+            d.horizon, format_yen(d.stay_cost), format_yen(d.move_cost),
+            if d.cheaper == Choice::Stay { "renewing" } else { "moving" }, format_yen(d.saving)
+        );
+    }
+    if result.horizon_too_short {
+        body += "<br>The comparison horizon ends before the current contract does, so there is nothing to compare.";
+    }
+    if let Some(p) = &result.plan {
+        body += &format!("<br>Cheapest plan over {} months ({} yen in all):", p.horizon, format_yen(p.total));
+        for s in &p.segments {
+            let home = match (s.home, s.moved) {
+                (Home::Current, _) => "current apartment",
+                (Home::Replacement, true) => "move to a new place",
+                (Home::Replacement, false) => "renew the new place",
+            };
+            body += &format!(
+                "<br>Months {}–{}: {}, {} yen", s.start + 1, s.start + s.months, home, format_yen(s.cost)
+            );
+        }
+    }
+    if let Some(r) = &result.rent_vs_buy {
+        body += &format!(
+            "<br>Over {} years, renting costs {} yen and buying {} yen ({} yen/month of mortgage): <strong>{} saves {} yen</strong>.",
+            r.horizon_years, format_yen(r.rent_total), format_yen(r.buy_total), format_yen(r.monthly_payment),
+            if r.cheaper == Tenure::Buy { "buying" } else { "renting" }, format_yen((r.rent_total - r.buy_total).abs())
+        );
+        match r.crossover_year {
+            Some(y) => body += &format!("<br>Buying costs less from year {} on.", y),
+            None => body += "<br>Buying never costs less over the years compared.",
+        }
+        for v in &r.by_rate {
+            body += &format!("<br>At a {:.2}% mortgage rate, buying costs {} yen.", v.mortgage_rate, format_yen(v.buy_total));
+        }
+        for v in &r.by_resale {
+            body += &format!("<br>Selling for {} yen, buying costs {} yen.", format_yen(v.resale_value), format_yen(v.buy_total));
+        }
+    }
+    if result.discount_rate > 0.0 {
+        body += &format!(
+            "<br>At a {}% discount rate, everything is worth {} yen today ({} yen of it lost returns on the deposit), the same as paying {} yen at the start of every month.",
+            result.discount_rate, format_yen(result.present_value),
+            format_yen(result.deposit_cost), format_yen(result.level_monthly)
+        );
+    }
+    if result.terms.len() > 1 {
+        for (i, t) in result.terms.iter().enumerate() {
+            body += &format!(
+                "<br>Term {} ({} months): rent {} yen + management fee {} yen",
+                i + 1, t.months, format_yen(t.rent), format_yen(t.management_fee)
+            );
+            if i > 0 {
+                let fee = if result.contract_type == ContractType::Ordinary { "renewal fee" } else { "re-contract fee" };
+                body += &format!(", {} {} yen", fee, format_yen(t.renewal_fee));
+            }
+        }
+    }
+    body
+}
+
+fn moving_kind_name(kind: MovingKind) -> &'static str {
+    match kind {
+        MovingKind::Movers => "yen movers",
+        MovingKind::Curtains => "yen curtains",
+        MovingKind::Lighting => "yen lighting",
+        MovingKind::AirConditioner => "yen AC installation",
+        MovingKind::InternetSetup => "yen internet setup",
+        MovingKind::Disposal => "yen disposal",
+        MovingKind::Other => "yen other",
+    }
+}
+
+fn running_kind_name(kind: RunningKind) -> &'static str {
+    match kind {
+        RunningKind::Electricity => "yen/month electricity",
+        RunningKind::Gas => "yen/month gas",
+        RunningKind::Water => "yen/month water",
+        RunningKind::Internet => "yen/month internet",
+        RunningKind::Other => "yen/month other",
+    }
+}
@@ -0,0 +1,458 @@
+use crate::calculator::RentResult;
+use crate::constants::{ContractType, ItemKind, MovingKind, RunningKind, UnitId};
+use crate::decision::{Choice, Home};
+use crate::purchase::Tenure;
+use crate::i18n::{Catalog, escape_html};
+use crate::yen::format_yen;
+
+pub const CATALOG: Catalog = Catalog {
+    title: "実質家賃計算機",
+    calc_button: "計算する",
+    live_switch: "入力に合わせて自動で計算する",
+    result_header: "計算結果",
+    result_title_placeholder: "ここに計算結果が出ます",
+    result_body_placeholder: "結果の説明など",
+    pending_title: "計算待ち",
+    pending_body: "入力が揃うと計算結果が表示されます",
+    export_label: "Export （入力値を保存したい場合はこちらをコピペしてください）",
+    import_label: "Import （入力値を復元したい場合はこちらにペーストして復元ボタンを押してください）",
+    import_placeholder: "開発中です",
+    restore_button: "復元する",
+    back_to_top: "上に戻る",
+    made_by: "made with ♥ by esplo: ",
+    yen_invalid: "金額として読み取れません（例: 52000, 52,000, 5.2万）",
+    listing_label: "物件情報から入力（物件ページの費用欄をそのまま貼り付けてください）",
+    listing_placeholder: "賃料 8.5万円 管理費 5,000円 敷金1ヶ月 礼金1ヶ月 保証会社利用必須 初回50%",
+    listing_button: "読み取る",
+    listing_report,
+    schedule_title: "更新時の変更",
+    schedule_detail: "更新のたびに賃料が値上げされたり、保証料が初回と異なる場合はここに追加してください。変更はその後の契約にも引き継がれます。",
+    schedule_renewal: "回目の更新から",
+    schedule_set: "円に変更",
+    schedule_percent: "%変更",
+    schedule_add: "変更を追加",
+    component_add: "＋ 内訳を追加（初回分と毎年分など）",
+    interval_suffix: "か月ごと",
+    free_rent_title: "フリーレントの条件",
+    free_rent_detail: "フリーレントは入居直後の月に適用されることが多い。管理費は無料にならないことが多い。最低居住期間（多くは1〜2年）より前に退去すると、無料分を返還する特約が付くことがある。最低居住期間がなければ0。",
+    free_rent_start: "か月目から",
+    free_rent_min_stay: "か月未満の退去で返還",
+    free_rent_covers_management_fee: "管理費も無料",
+    contract_type_label: "契約形態",
+    contract_type_detail: "普通借家は更新料を払えば住み続けられる。定期借家は期間満了で終了し、住み続けるには再契約（再契約料）が必要。再契約不可の物件では契約期間より長く住めない。",
+    contract_type_name,
+    move_in: (
+        "入居日（任意）",
+        "入れると、入居月の賃料・管理費を日割りで計算する。退去日も入れると居住期間はこの2つから求められる。",
+    ),
+    move_out: (
+        "退去日（任意）",
+        "入れると、退去月の賃料・管理費を日割りで計算する。入居日がなければ居住期間から逆算する。",
+    ),
+    area_title: "広さと人数（任意）",
+    area_detail: "広さの違う部屋は実質家賃だけでは比べにくい。専有面積（㎡）や居室の畳数、住む人数を入れると、1㎡・1畳・1人あたりの実質家賃を計算する。わからなければ0。",
+    square_meters: "㎡",
+    jo: "畳",
+    occupants: "人で住む",
+    income_title: "収入（任意）",
+    income_detail: "大家や保証会社の審査では、家賃（管理費込み）が月収の3分の1まで、つまり年収が家賃の36倍以上かどうかを見ることが多い。額面の年収、手取りの月収、家賃にかけてよい額面月収の割合を入れると、収入に対する実質家賃・生活コストの割合、審査に通りそうか、予算内の家賃の上限を計算する。年収が0なら計算しない。",
+    annual_income: "円/年（額面）",
+    take_home_pay: "円/月（手取り）",
+    budget_percent: "%まで",
+    subsidy_title: "住宅手当・借り上げ社宅（任意）",
+    subsidy_detail: "勤務先が負担する分。住宅手当は毎月の定額、借り上げ社宅は家賃の一定割合（月あたりの上限つき）と、会社が払う初期費用などの項目で指定する。家賃と管理費の合計が上限家賃を超えると支給されないことが多いので、その上限も入れられる。総額はそのままに、自己負担の総額と実質家賃を別に計算する。",
+    housing_allowance: "円/月の住宅手当",
+    subsidy_rent_limit: "円/月までの家賃が対象（0なら上限なし）",
+    employer_rent_percent: "%を会社が負担",
+    employer_rent_cap: "円/月まで（0なら上限なし）",
+    employer_paid_items: "会社が全額負担する項目",
+    home_office_title: "家事按分（任意）",
+    home_office_detail: "自宅で仕事をする場合に経費にする割合。全体の割合は各項目と光熱費などの生活コストに使い、項目ごとに変えたいときはその項目に割合を入れる。礼金や更新料は20万円以上だと繰延資産となり、契約期間（5年が上限）で月割りして償却する。退去時に残った分はその年に経費にする。日付があれば暦年、なければ入居からの年で集計する。",
+    home_office_percent: "%を事業用に",
+    home_office_common: "全体と同じ",
+    purchase_title: "購入との比較（任意）",
+    purchase_detail: "同じ期間、この物件を借り続ける（更新を含む）場合と家を買う場合を比べる。購入側は物件価格、頭金、住宅ローンの金利（月複利）と期間、管理費、修繕積立金、固定資産税、諸費用、期間の終わりの売却見込額（売却費用を引いた額、価値は直線的に下がるとする）で計算する。各年末に売却するとして、それまでの支払いから売却額とローン残高の差を引いた額を累計費用とする。比較期間が0なら比較しない。",
+    purchase_price: "円（物件価格）",
+    down_payment: "円（頭金）",
+    closing_costs: "円（諸費用）",
+    resale_value: "円（売却見込額）",
+    mortgage_rate: "%（ローン金利）",
+    mortgage_years: "年ローン",
+    owner_management_fee: "円/月（管理費）",
+    repair_reserve: "円/月（修繕積立金）",
+    property_tax: "円/年（固定資産税）",
+    purchase_horizon: "年間で比較",
+    split_title: "費用の分担（任意）",
+    split_detail: "2〜3人で住むとき、部屋の広さに応じて家賃を分けたり、駐車場代を1人が払ったりする。住む人の名前をカンマ区切りで入れると、各自の初期費用・月々の支払い・実質家賃を計算する。項目ごとの分担を追加しなければ均等に割る。固定額は請求1回あたり（家賃なら毎月）の金額で、残りは割合どおり、割合がなければ全員で均等に割る。",
+    split_people: "例: 太郎, 花子",
+    split_person: "名前",
+    split_percent: "%",
+    split_fixed: "円",
+    split_add: "分担を追加",
+    current_home_title: "今の住まい（任意）",
+    current_home_detail: "退去には解約予告（多くは1か月前）が必要で、新居の契約開始から旧居の契約終了まで家賃を二重に払うことになる。家賃と管理費の合計、予告期間、退去日（決まっていれば）を入れる。退去日がなければ新居の契約開始日に予告したものとする。引っ越さない場合は家賃を0にする。",
+    current_rent: "円/月",
+    current_notice: "か月前予告",
+    current_move_out: "旧居の退去日",
+    overlap_in_total: "新居の総額に含める",
+    moving_title: "引越し費用（任意）",
+    moving_detail: "物件に払うもの以外に、引越し業者、カーテンや照明（日本の賃貸では付いていないことが多い）、エアコンの取り付け、インターネットの開通工事、不用品の処分などにお金がかかる。「目安を入れる」で空欄に一人暮らしの目安が入る。合計に含めると居住期間で割って実質月額に加える。",
+    moving_kind_name,
+    moving_presets: "目安を入れる",
+    moving_in_total: "合計・月額に含める",
+    running_title: "光熱費・通信費（任意）",
+    running_detail: "家賃が同じでも、インターネット無料か、水道が定額（水道定額）か、都市ガスかプロパンガス（都市ガスの2倍近いことが多い）かオール電化かで、毎月の出費は大きく変わる。月の見込み額を入れ、家賃や管理費に含まれるものはチェックする。「目安を入れる」で空欄に一人暮らし・都市ガスの目安が入る。",
+    running_kind_name,
+    running_included: "家賃に含まれる",
+    running_presets: "目安を入れる",
+    renewal_title: "更新か引っ越しか",
+    renewal_detail: "今の住まいの更新が近いとき、更新して住み続ける場合と、契約満了で上の物件に引っ越す場合を比べ、その後の更新ごとに住み続けるか引っ越すかの最も安い組み合わせも求める。今の家賃は「今の住まい」の欄の金額を使う。更新までの月数、更新料、更新後の家賃、契約期間、比較する期間を入れる。引越し費用は「引越し費用」の欄の金額を、引っ越すたびにかかるものとして使う。比較期間が0なら比べない。",
+    renewal_months_left: "か月後に更新",
+    renewal_fee: "円（更新料）",
+    renewed_rent: "円/月（更新後）",
+    renewal_contract_period: "か月ごとに更新",
+    renewal_horizon: "か月間で比較",
+    discount_rate: (
+        "割引率（年利%）",
+        "今払う1万円は後で払う1万円より重い。資産運用の利回りなどを入れると、支払いの現在価値と、それを毎月均等に払った場合の実質月額を計算する。敷金を預けている間に得られたはずの運用益も含める。0なら計算しない。",
+    ),
+    item_text,
+    unit_label,
+    result_title,
+    result_body,
+};
+
+fn item_text(kind: ItemKind) -> (&'static str, &'static str) {
+    match kind {
+        ItemKind::Rent => (
+            "賃料",
+            "毎月請求される家賃の基本。これだけで済むシンプルなルールなら、こんなツールは要らなかった。",
+        ),
+        ItemKind::ManagementFee => (
+            "共益費・管理費",
+            "毎月請求される基本的な料金その2。家賃と並んで市民権を得ているので、大々的に表示される。家賃○か月分、という費用にはこれが含まれないことが多いのでややこしい。",
+        ),
+        ItemKind::Deposit => (
+            "敷金",
+            "退去時に原状回復費用などを差し引いて返ってくる預け金。費用には含めないが、その間お金が寝ることになるので、割引率を入れるとその機会費用を計算する。",
+        ),
+        ItemKind::KeyMoney => (
+            "礼金（敷引）",
+            "初期費用として請求されるメジャーなものその1。だいたい賃料の1か月分。入居時にオーナーへ感謝の気持ちを込めて払うものだったらしいが、企業が管理している物件だと形骸化している気がしてならない。早く埋めたい物件では真っ先に安くされる。オーナーは礼金0にしているのに、仲介業者が増やして中抜きする場合もあるとか無いとか。",
+        ),
+        ItemKind::BrokerageFee => (
+            "仲介手数料",
+            "初期費用として請求されるメジャーなものその2。だいたい賃料の1か月分。こちらは仲介業者へ感謝の気持ちを込めて払うもの。仲介業者も当然お仕事なのでタダでは動けない。ただし、オーナーから成約報酬として十分な額がある場合、仲介手数料は0になる。据え置くこともある。",
+        ),
+        ItemKind::FreeRentPeriod => (
+            "フリーレント",
+            "この期間は賃料が発生しない。唯一増えると嬉しい値。ただし共益費はかかる点、期間中の退去は違約金が発生しうる点に注意。",
+        ),
+        ItemKind::GuaranteeFee => (
+            "保証料",
+            "保証会社必須の場合に発生することもある。初回に一括で家賃の50%というパターンがあったり。毎月家賃合計の1%というパターンもある。契約更新時にまた払うこともあるが、初回よりかなり安くなるのが通例。最近は保証会社利用必須の物件が増えており、しょうがない気はするがコストが余計にかさむ。",
+        ),
+        ItemKind::SupportFee => (
+            "24時間サポート",
+            "大手マンションだと発生しうる税金その1。なぜか強制なことが多いが、営業時間外どころか1回も使わないことが多い。任意加入の場合はちゃんと考えて選択しよう。",
+        ),
+        ItemKind::AssociationMembershipFee => (
+            "町内会費",
+            "町内会に貢献するための費用。なぜか強制支払い。賃貸だと通常ないため、これがある部屋は何か怪しい。",
+        ),
+        ItemKind::GuaranteeAdministrativeFee => (
+            "保証委託契約時事務手数料",
+            "保証会社を使う際に初回だけ発生する費用。事務処理という名目だが、妙に高い場合が多い。実際に何をしているのかは分からないので、言い値を払うことになる。",
+        ),
+        ItemKind::InsuranceFee => (
+            "保険料",
+            "火災保険など。だいたい選択の余地は無い。せめて会社やプランを選ばせてほしいところ。良心的な契約では複数社から選べたりするが、いずれにせよ選択肢は狭い。",
+        ),
+        ItemKind::BicycleSpaceFee => (
+            "自転車・バイク置き場使用料",
+            "二輪を持っている人には必要な費用。契約更新に合わせてこちらも更新されることが多い。独立した契約になることで、不要な人は払わなくて良い。良心的。",
+        ),
+        ItemKind::CarSpaceFee => (
+            "自動車置き場費用",
+            "自転車などと同様、通常契約するかを選べる。専有するスペースが段違いなので、都心では二輪と比べ凄まじい費用がかかる。そもそも部屋数に対し用意されている数が少ないため、自転車の100倍、バイクの10倍の費用がかかることも。",
+        ),
+        ItemKind::KeyChangeFee => (
+            "鍵交換費用",
+            "新築以外では入居時にかかることが多い謎費用の1つ。「セキュリティを守るため、鍵を変える必要があるんですよねー」ってそれオーナー側の負担でやるべきことでは。そもそも選択権がなく強制的に徴収される。もしオーナーが負担して交換してくれていたら感謝しよう。交換がされてなかったら……気をつけよう。",
+        ),
+        ItemKind::CleaningFee => (
+            "退去時清掃費用",
+            "敷金から引かれるものとは別に、固定でかかる費用。エアコン清掃などの名目で書かれているが、なぜか敷金と同じ括りにはならない。実質敷引き。原状回復の基準は明確になってきているので、こういう所で法律逃れの小銭集めをしているのかもしれない。大体特約に書かれているので、契約時には注意しよう。",
+        ),
+        ItemKind::ContractUpdateFee => (
+            "更新料",
+            "契約更新時に発生する費用。長く住んでいると、何故か更新料が発生し追い出し圧力をかけてくる。初期費用が美味しいからではないかと推測される。関東に多い風習。だいたい賃料の1か月。「更新事務手数料」となっている場合もあり、なぜか新しい人を入れるほうが事務作業がてこずらないという事態になっている。",
+        ),
+        ItemKind::RecontractFee => (
+            "再契約料",
+            "定期借家で期間満了後も住み続けるために再契約する際の費用。契約形態が定期借家のときだけ、更新料の代わりに計上される。",
+        ),
+        ItemKind::ContractPeriod => (
+            "契約期間",
+            "賃貸契約を更新するまでの期間。通常2年だが、定期借家契約だと色々。",
+        ),
+        ItemKind::LeasePeriod => (
+            "居住期間",
+            "何か月住む想定か。これによって礼金のダメージなどが変わってくる。基本的に長く住むほど安上がりだが、いい物件があれば早い段階で引っ越す方が安上がりな場合もある。マンスリーマンションなど短期の場合は日数でも指定できる（30日を1か月として扱う）。",
+        ),
+    }
+}
+
+fn listing_report(recognized: &[&str], defaulted: &[&str]) -> String {
+    if recognized.is_empty() {
+        return "読み取れる項目がありませんでした".to_string();
+    }
+    format!("読み取った項目: {}。そのままの項目: {}", recognized.join("、"), defaulted.join("、"))
+}
+
+fn unit_label(unit: UnitId) -> &'static str {
+    match unit {
+        UnitId::MonthlyYen => "円 / 月",
+        UnitId::OneShotYen => "円（初回のみ）",
+        UnitId::EveryContractYen => "円 / 契約",
+        UnitId::EveryContractUpdateYen => "円 / 契約更新",
+        UnitId::UpdateRentPercent => "% × 新賃料 / 契約更新",
+        UnitId::DailyYen => "円 / 日",
+        UnitId::WeeklyYen => "円 / 週",
+        UnitId::YearlyYen => "円 / 年",
+        UnitId::EveryNMonthsYen => "円 / Nか月",
+        UnitId::Month => "か月",
+        UnitId::Day => "日",
+    }
+}
+
+fn contract_type_name(contract_type: ContractType) -> &'static str {
+    match contract_type {
+        ContractType::Ordinary => "普通借家",
+        ContractType::FixedTerm => "定期借家（再契約可）",
+        ContractType::FixedTermNoRecontract => "定期借家（再契約不可）",
+    }
+}
+
+fn result_title(result: &RentResult) -> String {
+    if result.counted_in_days {
+        return format!("実質家賃: {}円/日（30日換算 {}円）", result.daily, result.monthly) + &living_cost(result);
+    }
+    format!("実質家賃: {}円/月", result.monthly) + &living_cost(result)
+}
+
+fn living_cost(result: &RentResult) -> String {
+    if result.running_monthly == 0 {
+        return String::new();
+    }
+    format!(" / 生活コスト: {}円/{}", result.living_monthly, if result.counted_in_days { "30日" } else { "月" })
+}
+
+fn result_body(result: &RentResult) -> String {
+    let mut body = format!("契約形態: {}。", contract_type_name(result.contract_type));
+    if result.exceeds_term {
+        body += "<strong>再契約できない定期借家のため、契約期間を超えて住むことはできません。</strong>";
+    }
+    if result.counted_in_days {
+        body += &format!(
+            "{}日住むと平均 {}円/日（30日換算 {}円）で、トータル{}円の支払いです。\
+            賃料+管理費のみだと30日換算{}円でトータル{}円の支払いでした",
+            result.lease_days, result.daily, result.monthly, result.total,
+            result.original_monthly, result.original_total
+        );
+    } else {
+        body += &format!(
+            "{}か月住むと平均 {}円/月 で、トータル{}円の支払いです。\
+            家賃+管理費のみだと{}円/月でトータル{}円の支払いでした。\
+            実質家賃との差は{}円/月です",
+            result.lease_period, result.monthly, result.total, result.original_monthly, result.original_total,
+            result.monthly - result.original_monthly
+        );
+    }
+    if let (Some(move_in), Some(move_out), Some(first), Some(last), Some(payment)) =
+        (result.move_in, result.move_out, result.first_month, result.last_month, result.move_in_payment) {
+        body += &format!(
+            "<br>{}〜{}: 入居月は日割りで{}円、退去月は{}円（賃料+管理費）。翌月分の前家賃も求められる場合、入居時は{}円になります",
+            move_in, move_out, format_yen(first), format_yen(last),
+            format_yen(payment)
+        );
+    }
+    if result.free_rent_clawed_back {
+        body += "<br><strong>最低居住期間より前に退去するため、フリーレント分を返還することになります。</strong>";
+    } else if result.free_rent_discount > 0 {
+        body += &format!("<br>フリーレントで{}円安くなっています", format_yen(result.free_rent_discount));
+    }
+    if result.overlap_cost > 0 {
+        body += &format!(
+            "<br>旧居の家賃が{}日分重なり{}円かかります（{}）",
+            result.overlap_days, format_yen(result.overlap_cost),
+            if result.overlap_in_total { "総額に含む" } else { "引越し費用として別計上" }
+        );
+    }
+    if let Some(date) = result.notice_by {
+        body += &format!("<br>旧居の解約予告は{}までに", date);
+    }
+    let mut per = vec![];
+    if let Some(v) = result.per_square_meter {
+        per.push(format!("1㎡あたり{}円", format_yen(v)));
+    }
+    if let Some(v) = result.per_jo {
+        per.push(format!("1畳あたり{}円", format_yen(v)));
+    }
+    if result.occupants > 1 {
+        per.push(format!("1人あたり{}円", format_yen(result.per_person)));
+    }
+    if !per.is_empty() {
+        body += &format!("<br>実質家賃は{}です", per.join("、"));
+    }
+    if let Some(s) = &result.subsidy {
+        if s.eligible {
+            body += &format!(
+                "<br>会社負担{}円を除くと、自己負担は総額{}円、実質家賃<strong>{}円/月</strong>です",
+                format_yen(s.employer_total), format_yen(s.net_total), format_yen(s.net_monthly)
+            );
+        } else {
+            body += &format!(
+                "<br><strong>家賃と管理費が上限の{}円/月を超えるため、会社の補助は受けられません</strong>",
+                format_yen(s.rent_limit)
+            );
+        }
+    }
+    if let Some(h) = &result.home_office {
+        let deferred = h.items.iter().filter(|i| i.deferred).count();
+        body += &format!(
+            "<br>事業用は総額{}円{}",
+            format_yen(h.items.iter().map(|i| i.business).sum::<i32>() + h.utilities_business),
+            if deferred > 0 { "（繰延資産の償却を含む）" } else { "" }
+        );
+        for y in &h.years {
+            body += &format!(
+                "<br>{}: 支払{}円のうち事業用{}円、経費{}円",
+                if h.calendar { format!("{}年", y.year) } else { format!("{}年目", y.year) },
+                format_yen(y.paid), format_yen(y.business), format_yen(y.deductible)
+            );
+            if y.amortized > 0 {
+                body += &format!("（うち償却{}円）", format_yen(y.amortized));
+            }
+        }
+    }
+    if let Some(a) = &result.affordability {
+        body += &format!("<br>実質家賃は額面月収の{:.1}%", a.rent_of_gross);
+        if let Some(t) = a.rent_of_take_home {
+            body += &format!("（手取りの{:.1}%）", t);
+        }
+        body += &format!("、生活コストは額面の{:.1}%", a.living_of_gross);
+        if let Some(t) = a.living_of_take_home {
+            body += &format!("（手取りの{:.1}%）", t);
+        }
+        body += &format!(
+            "です。<br>{}（審査の目安は家賃+管理費{}円まで）。額面月収の{:.1}%に収めるなら実質家賃{}円/月まで",
+            if a.passes_screening { "入居審査の収入基準は満たしそうです" } else { "<strong>入居審査の収入基準を満たさないかもしれません</strong>" },
+            format_yen(a.screening_max_rent), a.budget_percent, format_yen(a.max_rent)
+        );
+    }
+    for s in &result.shares {
+        body += &format!(
+            "<br>{}: 初期費用{}円、月々{}円、実質家賃{}円/月",
+            escape_html(&s.name), format_yen(s.upfront), format_yen(s.monthly), format_yen(s.effective)
+        );
+    }
+    if result.running_monthly > 0 {
+        body += &format!(
+            "<br>光熱費・通信費が月{}円かかり、居住期間の生活コストはトータル{}円です",
+            format_yen(result.running_monthly), format_yen(result.living_total)
+        );
+    }
+    if result.moving_total > 0 {
+        let items: Vec<String> = result.moving_costs.iter()
+            .map(|i| format!("{}{}", format_yen(i.value), moving_kind_name(i.kind)))
+            .collect();
+        body += &format!(
+            "<br>引越し費用{}円（{}）: {}",
+            format_yen(result.moving_total), items.join("、"),
+            if result.moving_in_total { "合計に含む" } else { "合計には含まない" }
+        );
+    }
+    if let Some(d) = &result.decision {
+        body += &format!(
+            "<br>{}か月間で比べると、更新して住み続ける場合{}円、引っ越す場合{}円で、<strong>{}方が{}円安い</strong>",
+            d.horizon, format_yen(d.stay_cost), format_yen(d.move_cost),
+            if d.cheaper == Choice::Stay { "住み続ける" } else { "引っ越す" }, format_yen(d.saving)
+        );
+    }
+    if result.horizon_too_short {
+        body += "<br>比較期間が今の契約の終了より短いため、更新と引越しは比べられません";
+    }
+    if let Some(p) = &result.plan {
+        body += &format!("<br>{}か月間で最も安い計画（計{}円）:", p.horizon, format_yen(p.total));
+        for s in &p.segments {
+            let home = match (s.home, s.moved) {
+                (Home::Current, _) => "今の住まい",
+                (Home::Replacement, true) => "引っ越して新居",
+                (Home::Replacement, false) => "新居を更新",
+            };
+            body += &format!(
+                "<br>{}〜{}か月目: {} {}円", s.start + 1, s.start + s.months, home, format_yen(s.cost)
+            );
+        }
+    }
+    if let Some(r) = &result.rent_vs_buy {
+        body += &format!(
+            "<br>{}年間で、賃貸は{}円、購入は{}円（ローン返済月{}円）: <strong>{}が{}円安い</strong>",
+            r.horizon_years, format_yen(r.rent_total), format_yen(r.buy_total), format_yen(r.monthly_payment),
+            if r.cheaper == Tenure::Buy { "購入" } else { "賃貸" }, format_yen((r.rent_total - r.buy_total).abs())
+        );
+        match r.crossover_year {
+            Some(y) => body += &format!("<br>{}年目以降は購入の方が安くなります", y),
+            None => body += "<br>期間中に購入の方が安くなることはありません",
+        }
+        for v in &r.by_rate {
+            body += &format!("<br>金利{:.2}%なら購入{}円", v.mortgage_rate, format_yen(v.buy_total));
+        }
+        for v in &r.by_resale {
+            body += &format!("<br>売却額{}円なら購入{}円", format_yen(v.resale_value), format_yen(v.buy_total));
+        }
+    }
+    if result.discount_rate > 0.0 {
+        body += &format!(
+            "<br>割引率{}%での現在価値は{}円（うち敷金の運用機会損失{}円）、毎月均等払いに換算すると月{}円です",
+            result.discount_rate, format_yen(result.present_value),
+            format_yen(result.deposit_cost), format_yen(result.level_monthly)
+        );
+    }
+    if result.terms.len() > 1 {
+        for (i, t) in result.terms.iter().enumerate() {
+            body += &format!(
+                "<br>{}期目（{}か月）: 賃料{}円 + 管理費{}円",
+                i + 1, t.months, format_yen(t.rent), format_yen(t.management_fee)
+            );
+            if i > 0 {
+                let fee = if result.contract_type == ContractType::Ordinary { "更新料" } else { "再契約料" };
+                body += &format!("、{}{}円", fee, format_yen(t.renewal_fee));
+            }
+        }
+    }
+    body
+}
+
+fn moving_kind_name(kind: MovingKind) -> &'static str {
+    match kind {
+        MovingKind::Movers => "円（引越し業者）",
+        MovingKind::Curtains => "円（カーテン）",
+        MovingKind::Lighting => "円（照明）",
+        MovingKind::AirConditioner => "円（エアコン取付）",
+        MovingKind::InternetSetup => "円（ネット開通）",
+        MovingKind::Disposal => "円（不用品処分）",
+        MovingKind::Other => "円（その他）",
+    }
+}
+
+fn running_kind_name(kind: RunningKind) -> &'static str {
+    match kind {
+        RunningKind::Electricity => "円/月（電気）",
+        RunningKind::Gas => "円/月（ガス）",
+        RunningKind::Water => "円/月（水道）",
+        RunningKind::Internet => "円/月（インターネット）",
+        RunningKind::Other => "円/月（その他）",
+    }
+}
@@ -1,3 +1,5 @@
+use std::cell::Cell;
+use std::rc::Rc;
 use std::string::ToString;
 
 use thiserror::Error;
@@ -7,16 +9,37 @@ use web_sys::*;
 
 use calculator::*;
 
-use crate::constants::InputId;
+use crate::constants::{InputId, ItemKind, UnitId};
 use crate::form_items::HtmlItem;
 use crate::utils::{HtmlAttr, make_tag};
-use crate::utils::fetch_value;
+use crate::i18n::{Catalog, Lang};
+use crate::utils::{fetch_value, peek_value, to_js};
+use crate::widget::MountOptions;
 
+mod affordability;
+mod api;
+mod area;
 mod calculator;
+mod cash_flow;
+mod components;
 mod constants;
+mod date;
+mod decision;
 mod form_info;
 mod form_items;
+mod home_office;
+mod i18n;
+mod listing;
+mod moving;
+mod purchase;
+mod ranking;
+mod running_costs;
+mod schedule;
+mod split;
+mod subsidy;
 mod utils;
+mod widget;
+mod yen;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -24,22 +47,22 @@ mod utils;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+/// Wait after the last keystroke before recalculating in live mode.
+const LIVE_DEBOUNCE_MS: i32 = 300;
+
 #[wasm_bindgen]
 extern "C" {
     fn alert(s: &str);
 }
 
-#[wasm_bindgen]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct RentInfo {
-    rent: i32,
-}
-
-
 #[derive(Error, Debug)]
 pub enum HtmlError {
     #[error("should have #{0} on the page")]
     CannotGetElement(String),
+    #[error("the mount target should be in a document")]
+    NotInDocument,
+    #[error("a calculator is already mounted on the page")]
+    AlreadyMounted,
 }
 
 impl From<HtmlError> for JsValue {
@@ -52,62 +75,94 @@ impl From<HtmlError> for JsValue {
 pub enum InputError {
     #[error("Cannot Parse value #{0}")]
     CannotParse(String),
+    #[error("{1} is not available for {0}")]
+    UnsupportedUnit(ItemKind, UnitId),
+}
+
+impl From<InputError> for JsValue {
+    fn from(w: InputError) -> JsValue {
+        JsValue::from(js_sys::Error::new(&format!("{}", w)))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CalcError {
+    #[error("{0} should be greater than 0")]
+    InvalidPeriod(ItemKind),
+    #[error("The planning horizon should be longer than the months left until the renewal")]
+    HorizonTooShort,
+    #[error("The contract period after the renewal should be greater than 0")]
+    InvalidRenewalPeriod,
+    #[error("At least one person should live in the property")]
+    NoOccupants,
+}
+
+impl From<CalcError> for JsValue {
+    fn from(w: CalcError) -> JsValue {
+        JsValue::from(js_sys::Error::new(&format!("{}", w)))
+    }
 }
 
 
 #[wasm_bindgen]
 pub fn calc(document: &Document) -> Result<(), JsValue> {
-    let rent_elem = RentElements::build(document)?;
+    calc_with(document, &MountOptions::default(), false)
+}
 
-    {
-        let export_form = document
-            .get_element_by_id(&InputId::JsonExportTextArea.to_string())
-            .unwrap_or_else(|| panic!("should have #{} on the page", InputId::JsonExportTextArea.to_string()));
+/// In `live` mode, incomplete input shows a pending message instead of an error.
+fn calc_with(document: &Document, options: &MountOptions, live: bool) -> Result<(), JsValue> {
+    let rent_elem = match RentElements::build(document, &options.initial, !live) {
+        Ok(e) => e,
+        Err(_) if live => return write_pending(document, options.lang.catalog()),
+        Err(e) => return Err(e),
+    };
+
+    // the export area is optional when mounted
+    if let Some(export_form) = document.get_element_by_id(&InputId::JsonExportTextArea.to_string()) {
         let form_place = export_form
             .dyn_ref::<web_sys::HtmlTextAreaElement>()
-            .unwrap_or_else(|| panic!("#{} should be an `HtmlTextareaElement`", InputId::JsonExportTextArea.to_string()));
+            .unwrap_or_else(|| panic!("#{} should be an `HtmlTextareaElement`", InputId::JsonExportTextArea));
 
         form_place.set_inner_html(&rent_elem.to_json_string());
     }
 
     let calculator = RentCalculator {};
-    let result = calculator.calc(rent_elem)?;
+    let result = match calculator.calc(rent_elem) {
+        Ok(r) => r,
+        Err(_) if live => return write_pending(document, options.lang.catalog()),
+        Err(e) => return Err(e.into()),
+    };
 
-    // write result title
-    {
-        let form_place = document
-            .get_element_by_id(&InputId::CalcResultTitle.to_string())
-            .expect("should have #HtmlAreaId::CalcResult on the page");
-        let html_form_place = form_place
-            .dyn_ref::<web_sys::HtmlElement>()
-            .expect("#forms should be an `HtmlElement`");
-        html_form_place.set_inner_html(&format!(
-            "実質家賃: {}円/月",
-            result.monthly
-        ));
+    let catalog = options.lang.catalog();
+    write_result(document, &(catalog.result_title)(&result), &(catalog.result_body)(&result));
+
+    if let Some(f) = &options.on_result {
+        f.call1(&JsValue::NULL, &to_js(&result)?)?;
     }
-    {
+    Ok(())
+}
+
+fn write_pending(document: &Document, catalog: &Catalog) -> Result<(), JsValue> {
+    write_result(document, catalog.pending_title, catalog.pending_body);
+    Ok(())
+}
+
+fn write_result(document: &Document, title: &str, body: &str) {
+    for (id, text) in [(InputId::CalcResultTitle, title), (InputId::CalcResultBody, body)] {
         let form_place = document
-            .get_element_by_id(&InputId::CalcResultBody.to_string())
-            .expect("should have #HtmlAreaId::CalcResult on the page");
+            .get_element_by_id(&id.to_string())
+            .unwrap_or_else(|| panic!("should have #{} on the page", id));
         let html_form_place = form_place
             .dyn_ref::<web_sys::HtmlElement>()
-            .expect("#forms should be an `HtmlElement`");
-        html_form_place.set_inner_html(&format!(
-            "{}か月住むと平均 {}円/月 で、トータル{}円の支払いです。\
-            家賃+管理費のみだと{}円/月でトータル{}円の支払いでした。\
-            実質家賃との差は{}円/月です",
-            result.lease_period, result.monthly, result.total, result.original_monthly, result.original_total,
-            result.monthly - result.original_monthly
-        ));
+            .unwrap_or_else(|| panic!("#{} should be an `HtmlElement`", id));
+        html_form_place.set_inner_html(text);
     }
-    Ok(())
 }
 
 
 #[wasm_bindgen]
 pub fn restore(document: &Document) -> Result<(), JsValue> {
-    let input_json = fetch_value::<String>(&document, InputId::JsonImportTextArea)?;
+    let input_json = fetch_value::<String>(document, InputId::JsonImportTextArea)?;
     serde_json::from_str(&input_json)
         .map_err(|e| JsValue::from(&format!("{}", e)))
         .map(|_: RentElements| {
@@ -116,27 +171,29 @@ pub fn restore(document: &Document) -> Result<(), JsValue> {
 }
 
 
-fn construct() -> Result<Node, JsValue> {
-    let window = web_sys::window().expect("no global `window` exists");
-    let document = window.document().expect("should have a document on window");
-
-    let form_place = document
-        .get_element_by_id("forms")
-        .expect("should have #forms on the page");
-
-    let html_form_place = form_place
-        .dyn_ref::<HtmlElement>()
-        .expect("#forms should be an `HtmlElement`");
+fn construct(document: &Document, parent: &HtmlElement, options: Rc<MountOptions>) -> Result<Element, JsValue> {
+    let document = document.clone();
+    let catalog = options.lang.catalog();
 
-    let forms = form_info::create_form();
+    let forms = form_info::create_form(&options);
     let form_html = forms.to_html(&document)?;
 
     let whole_wrapper = make_tag(&document, "div",
-                                 vec![HtmlAttr { name: "name", value: "wrapper" }],
-                                 None, Some(&html_form_place))?;
+                                 vec![
+                                     HtmlAttr { name: "name", value: "wrapper" },
+                                     HtmlAttr { name: "lang", value: options.lang.code() },
+                                 ],
+                                 None, Some(parent))?;
+
+    if options.header {
+        make_lang_select(&document, &whole_wrapper, parent, options.clone())?;
+        make_tag(&document, "h1", vec![],
+                 Some(catalog.title), Some(&whole_wrapper))?;
+    }
 
-    make_tag(&document, "h1", vec![],
-             Some("実質家賃計算機"), Some(&whole_wrapper))?;
+    if options.listing {
+        make_listing(&document, &whole_wrapper, options.clone())?;
+    }
 
     let form_wrapper = make_tag(&document, "div",
                                 vec![
@@ -156,12 +213,13 @@ fn construct() -> Result<Node, JsValue> {
                                vec![
                                    HtmlAttr { name: "type", value: "button" },
                                    HtmlAttr { name: "class", value: "btn btn-primary btn-lg btn-block" },
-                               ], Some("計算する"), Some(&calc_button_wrapper))?;
+                               ], Some(catalog.calc_button), Some(&calc_button_wrapper))?;
 
     {
         let document = document.clone();
+        let options = options.clone();
         let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
-            if let Err(e) = calc(&document) {
+            if let Err(e) = calc_with(&document, &options, false) {
                 console::error_1(&e);
             }
         }) as Box<dyn FnMut(_)>);
@@ -169,13 +227,15 @@ fn construct() -> Result<Node, JsValue> {
         closure.forget();
     }
 
+    make_live_switch(&document, &calc_button_wrapper, &form_wrapper, options.clone())?;
+
     {
         let result_card_wrapper = make_tag(&document, "div",
                                            vec![HtmlAttr { name: "class", value: "card border-primary mb-3" }],
                                            None, Some(&whole_wrapper))?;
         make_tag(&document, "div",
                  vec![HtmlAttr { name: "class", value: "card-header" }],
-                 Some("計算結果"), Some(&result_card_wrapper))?;
+                 Some(catalog.result_header), Some(&result_card_wrapper))?;
         let card_body = make_tag(&document, "div",
                                  vec![HtmlAttr { name: "class", value: "card-body text-primary" }],
                                  None, Some(&result_card_wrapper))?;
@@ -184,49 +244,253 @@ fn construct() -> Result<Node, JsValue> {
                      HtmlAttr { name: "class", value: "card-title" },
                      HtmlAttr { name: "id", value: &InputId::CalcResultTitle.to_string() },
                  ],
-                 Some("ここに計算結果が出ます"), Some(&card_body))?;
+                 Some(catalog.result_title_placeholder), Some(&card_body))?;
         make_tag(&document, "p",
                  vec![
                      HtmlAttr { name: "class", value: "card-text" },
                      HtmlAttr { name: "id", value: &InputId::CalcResultBody.to_string() },
                  ],
-                 Some("結果の説明など"), Some(&card_body))?;
+                 Some(catalog.result_body_placeholder), Some(&card_body))?;
+    }
+
+    if options.export {
+        make_export(&document, &whole_wrapper, catalog)?;
+    }
+
+    if options.footer {
+        make_footer(&document, &whole_wrapper, catalog)?;
+    }
+
+    Ok(whole_wrapper)
+}
+
+/// Text area to fill the form from pasted listing text.
+fn make_listing(document: &Document, parent: &Element, options: Rc<MountOptions>) -> Result<(), JsValue> {
+    let catalog = options.lang.catalog();
+    let wrapper = make_tag(document, "div",
+                           vec![HtmlAttr { name: "class", value: "form-group" }],
+                           None, Some(parent))?;
+    make_tag(document, "label",
+             vec![HtmlAttr { name: "for", value: &InputId::ListingTextArea.to_string() }],
+             Some(catalog.listing_label), Some(&wrapper))?;
+    make_tag(document, "textarea",
+             vec![
+                 HtmlAttr { name: "id", value: &InputId::ListingTextArea.to_string() },
+                 HtmlAttr { name: "class", value: "form-control" },
+                 HtmlAttr { name: "rows", value: "2" },
+                 HtmlAttr { name: "placeholder", value: catalog.listing_placeholder },
+             ], None, Some(&wrapper))?;
+    let button = make_tag(document, "button",
+                          vec![
+                              HtmlAttr { name: "type", value: "button" },
+                              HtmlAttr { name: "class", value: "btn btn-secondary btn-sm mt-1" },
+                          ], Some(catalog.listing_button), Some(&wrapper))?;
+    make_tag(document, "small",
+             vec![
+                 HtmlAttr { name: "id", value: &InputId::ListingReport.to_string() },
+                 HtmlAttr { name: "class", value: "form-text text-muted" },
+             ], None, Some(&wrapper))?;
+
+    let document = document.clone();
+    let closure = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
+        if let Err(e) = import_listing(&document, &options) {
+            console::error_1(&e);
+        }
+    }) as Box<dyn FnMut(_)>);
+    button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+    Ok(())
+}
+
+fn import_listing(document: &Document, options: &MountOptions) -> Result<(), JsValue> {
+    let catalog = options.lang.catalog();
+    let text = fetch_value::<String>(document, InputId::ListingTextArea)?;
+    let current = RentElements::build(document, &options.initial, false)
+        .unwrap_or_else(|_| options.initial.clone());
+    let import = listing::parse_listing(&text, &current);
+
+    // items hidden from the form cannot be filled
+    let recognized: Vec<ItemKind> = import.recognized.iter().copied()
+        .filter(|k| options.items.contains(k))
+        .collect();
+    for kind in recognized.iter() {
+        let item = import.elements.item(*kind);
+        if let Some(input) = document.get_element_by_id(&kind.input_id().to_string())
+            .and_then(|e| e.dyn_into::<HtmlInputElement>().ok()) {
+            input.set_value(&item.value.to_string());
+            // refreshes the yen preview
+            input.dispatch_event(&Event::new("input")?)?;
+        }
+        if let Some(select) = document.get_element_by_id(&kind.unit_input_id().to_string())
+            .and_then(|e| e.dyn_into::<HtmlSelectElement>().ok()) {
+            select.set_value(&(item.unit as i32).to_string());
+        }
+    }
+
+    let names = |kinds: Vec<ItemKind>| -> Vec<&str> {
+        kinds.into_iter()
+            .filter(|k| options.items.contains(k))
+            .map(|k| (catalog.item_text)(k).0)
+            .collect()
+    };
+    let defaulted = import.defaulted().into_iter().chain(
+        import.recognized.iter().copied().filter(|k| !recognized.contains(k))).collect();
+    if let Some(report) = document.get_element_by_id(&InputId::ListingReport.to_string()) {
+        report.set_inner_html(&(catalog.listing_report)(&names(recognized), &names(defaulted)));
+    }
+
+    calc_with(document, options, true)
+}
+
+/// Rebuilds the whole UI in the selected language, keeping the current input.
+fn make_lang_select(document: &Document, wrapper: &Element, parent: &HtmlElement,
+                    options: Rc<MountOptions>) -> Result<(), JsValue> {
+    let select = make_tag(document, "select",
+                          vec![
+                              HtmlAttr { name: "id", value: &InputId::LangSelect.to_string() },
+                              HtmlAttr { name: "class", value: "custom-select custom-select-sm w-auto float-right" },
+                          ], None, Some(wrapper))?;
+    for lang in Lang::ALL.iter() {
+        let value = format!("{}", *lang as i32);
+        let mut attr = vec![HtmlAttr { name: "value", value: &value }];
+        if *lang == options.lang {
+            attr.push(HtmlAttr { name: "selected", value: "" });
+        }
+        make_tag(document, "option", attr, Some(lang.name()), Some(&select))?;
+    }
+
+    let document = document.clone();
+    let wrapper = wrapper.clone();
+    let parent = parent.clone();
+    let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+        let lang = peek_value::<i32>(&document, InputId::LangSelect).ok()
+            .and_then(|v| Lang::ALL.iter().copied().find(|l| *l as i32 == v));
+        let mut next = (*options).clone();
+        next.lang = lang.unwrap_or(options.lang);
+        next.initial = RentElements::build(&document, &options.initial, false)
+            .unwrap_or_else(|_| options.initial.clone());
+        next.live = document.get_element_by_id(&InputId::LiveSwitch.to_string())
+            .and_then(|e| e.dyn_into::<HtmlInputElement>().ok())
+            .map(|e| e.checked())
+            .unwrap_or(options.live);
+
+        let rebuilt = parent.remove_child(&wrapper)
+            .and_then(|_| construct(&document, &parent, Rc::new(next)));
+        if let Err(e) = rebuilt {
+            console::error_1(&e);
+        }
+    }) as Box<dyn FnMut(_)>);
+    select.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+
+    Ok(())
+}
+
+/// Recalculates on every edit inside `form_wrapper` while the switch is on.
+fn make_live_switch(document: &Document, parent: &Element, form_wrapper: &Element,
+                    options: Rc<MountOptions>) -> Result<(), JsValue> {
+    let live = Rc::new(Cell::new(options.live));
+
+    let switch_wrapper = make_tag(document, "div",
+                                  vec![HtmlAttr { name: "class", value: "custom-control custom-switch" }],
+                                  None, Some(parent))?;
+    let switch = make_tag(document, "input",
+                          vec![
+                              HtmlAttr { name: "type", value: "checkbox" },
+                              HtmlAttr { name: "class", value: "custom-control-input" },
+                              HtmlAttr { name: "id", value: &InputId::LiveSwitch.to_string() },
+                          ], None, Some(&switch_wrapper))?;
+    let switch: HtmlInputElement = switch.dyn_into::<HtmlInputElement>()?;
+    switch.set_checked(options.live);
+    make_tag(document, "label",
+             vec![
+                 HtmlAttr { name: "class", value: "custom-control-label" },
+                 HtmlAttr { name: "for", value: &InputId::LiveSwitch.to_string() },
+             ], Some(options.lang.catalog().live_switch), Some(&switch_wrapper))?;
+
+    {
+        let document = document.clone();
+        let options = options.clone();
+        let live = live.clone();
+        let switch_ref = switch.clone();
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            live.set(switch_ref.checked());
+            if live.get() {
+                if let Err(e) = calc_with(&document, &options, true) {
+                    console::error_1(&e);
+                }
+            }
+        }) as Box<dyn FnMut(_)>);
+        switch.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    // input/change events of every item bubble up to the wrapper
+    {
+        let document = document.clone();
+        let run_calc = Closure::wrap(Box::new(move || {
+            if let Err(e) = calc_with(&document, &options, true) {
+                console::error_1(&e);
+            }
+        }) as Box<dyn FnMut()>);
+        let timer = Cell::new(None);
+        let closure = Closure::wrap(Box::new(move |_: web_sys::Event| {
+            if !live.get() {
+                return;
+            }
+            let window = web_sys::window().expect("no global `window` exists");
+            if let Some(handle) = timer.take() {
+                window.clear_timeout_with_handle(handle);
+            }
+            match window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                run_calc.as_ref().unchecked_ref(), LIVE_DEBOUNCE_MS) {
+                Ok(handle) => timer.set(Some(handle)),
+                Err(e) => console::error_1(&e),
+            }
+        }) as Box<dyn FnMut(_)>);
+        form_wrapper.add_event_listener_with_callback("input", closure.as_ref().unchecked_ref())?;
+        form_wrapper.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+        closure.forget();
     }
 
-    make_tag(&document, "hr", vec![], None, Some(&whole_wrapper))?;
+    Ok(())
+}
 
-    make_tag(&document, "label",
+fn make_export(document: &Document, parent: &Element, catalog: &Catalog) -> Result<(), JsValue> {
+    make_tag(document, "hr", vec![], None, Some(parent))?;
+
+    make_tag(document, "label",
              vec![HtmlAttr { name: "for", value: &InputId::JsonExportTextArea.to_string() }],
-             Some("Export （入力値を保存したい場合はこちらをコピペしてください）"), Some(&whole_wrapper))?;
+             Some(catalog.export_label), Some(parent))?;
 
-    make_tag(&document, "textarea",
+    make_tag(document, "textarea",
              vec![
                  HtmlAttr { name: "id", value: &InputId::JsonExportTextArea.to_string() },
                  HtmlAttr { name: "class", value: "form-control" },
                  HtmlAttr { name: "rows", value: "3" },
                  HtmlAttr { name: "wrap", value: "soft" }
-             ], None, Some(&whole_wrapper))?;
+             ], None, Some(parent))?;
 
-    make_tag(&document, "label",
+    make_tag(document, "label",
              vec![HtmlAttr { name: "for", value: &InputId::JsonImportTextArea.to_string() }],
-             Some("Import （入力値を復元したい場合はこちらにペーストして復元ボタンを押してください）"), Some(&whole_wrapper))?;
+             Some(catalog.import_label), Some(parent))?;
 
-    make_tag(&document, "textarea",
+    make_tag(document, "textarea",
              vec![
                  HtmlAttr { name: "id", value: &InputId::JsonImportTextArea.to_string() },
                  HtmlAttr { name: "class", value: "form-control" },
                  HtmlAttr { name: "rows", value: "3" },
                  HtmlAttr { name: "wrap", value: "soft" },
-                 HtmlAttr { name: "placeholder", value: "開発中です" },
+                 HtmlAttr { name: "placeholder", value: catalog.import_placeholder },
                  HtmlAttr { name: "readonly", value: "" },
-             ], None, Some(&whole_wrapper))?;
-    let restore_button_wrapper = make_tag(&document, "div", vec![], None, Some(&whole_wrapper))?;
-    let restore_button = make_tag(&document, "button",
+             ], None, Some(parent))?;
+    let restore_button_wrapper = make_tag(document, "div", vec![], None, Some(parent))?;
+    let restore_button = make_tag(document, "button",
                                   vec![
                                       HtmlAttr { name: "type", value: "button" },
                                       HtmlAttr { name: "class", value: "btn btn-primary" },
                                       HtmlAttr { name: "disabled", value: "" },
-                                  ], Some("復元する"), Some(&restore_button_wrapper))?;
+                                  ], Some(catalog.restore_button), Some(&restore_button_wrapper))?;
 
     {
         let document = document.clone();
@@ -239,51 +503,49 @@ fn construct() -> Result<Node, JsValue> {
         closure.forget();
     }
 
-    make_footer(&document, &whole_wrapper)?;
-
-    Ok(form_place.into())
+    Ok(())
 }
 
-fn make_footer(document: &Document, parent: &Element) -> Result<Element, JsValue> {
-    make_tag(&document, "hr", vec![], None, Some(&parent))?;
-    let footer = make_tag(&document, "footer",
+fn make_footer(document: &Document, parent: &Element, catalog: &Catalog) -> Result<Element, JsValue> {
+    make_tag(document, "hr", vec![], None, Some(parent))?;
+    let footer = make_tag(document, "footer",
                           vec![
                               HtmlAttr { name: "class", value: "text-muted" },
-                          ], None, Some(&parent))?;
-    let container = make_tag(&document, "div",
+                          ], None, Some(parent))?;
+    let container = make_tag(document, "div",
                              vec![
                                  HtmlAttr { name: "class", value: "container-fluid" },
                              ], None, Some(&footer))?;
-    let right = make_tag(&document, "p",
+    let right = make_tag(document, "p",
                          vec![
                              HtmlAttr { name: "class", value: "float-right" },
                          ], None, Some(&container))?;
-    make_tag(&document, "a",
+    make_tag(document, "a",
              vec![
                  HtmlAttr { name: "href", value: "#" },
-             ], Some("上に戻る"), Some(&right))?;
-    let message = make_tag(&document, "p",
+             ], Some(catalog.back_to_top), Some(&right))?;
+    let message = make_tag(document, "p",
                            vec![], None, Some(&container))?;
-    make_tag(&document, "span",
-             vec![], Some("made with ♥ by esplo: "), Some(&message))?;
+    make_tag(document, "span",
+             vec![], Some(catalog.made_by), Some(&message))?;
 
     {
-        let sns = make_tag(&document, "a",
+        let sns = make_tag(document, "a",
                            vec![
                                HtmlAttr { name: "href", value: "https://twitter.com/esplo77" },
                            ], None, Some(&message))?;
-        make_tag(&document, "i",
+        make_tag(document, "i",
                  vec![
                      HtmlAttr { name: "class", value: "fa fa-twitter" },
                  ], None, Some(&sns))?;
     }
 
     {
-        let sns = make_tag(&document, "a",
+        let sns = make_tag(document, "a",
                            vec![
                                HtmlAttr { name: "href", value: "https://github.com/esplo/true-rent" },
                            ], None, Some(&message))?;
-        make_tag(&document, "i",
+        make_tag(document, "i",
                  vec![
                      HtmlAttr { name: "class", value: "fa fa-github" },
                  ], None, Some(&sns))?;
@@ -293,7 +555,24 @@ fn make_footer(document: &Document, parent: &Element) -> Result<Element, JsValue
 }
 
 #[wasm_bindgen(start)]
-pub fn run() -> Result<(), JsValue> {
+pub fn start() {
     utils::set_panic_hook();
-    construct().map(|_| ())
+}
+
+/// Builds the calculator UI into `#forms`.
+/// Not needed when only the `Scenario` API is used.
+#[wasm_bindgen]
+pub fn run() -> Result<(), JsValue> {
+    let window = web_sys::window().expect("no global `window` exists");
+    let document = window.document().expect("should have a document on window");
+
+    let form_place = document
+        .get_element_by_id("forms")
+        .expect("should have #forms on the page");
+
+    let html_form_place = form_place
+        .dyn_ref::<HtmlElement>()
+        .expect("#forms should be an `HtmlElement`");
+
+    construct(&document, html_form_place, Rc::new(MountOptions::default())).map(|_| ())
 }
@@ -0,0 +1,257 @@
+use strum::IntoEnumIterator;
+
+use crate::calculator::{RentElements, RentItem};
+use crate::constants::{ItemKind, UnitId};
+use crate::yen::{parse_yen, to_half_width};
+
+/// Words introducing each item on listing pages.
+/// Longer words win when they overlap, e.g. `保証会社` over `保証`.
+const KEYWORDS: &[(&str, Option<ItemKind>)] = &[
+    ("賃料", Some(ItemKind::Rent)),
+    ("家賃", Some(ItemKind::Rent)),
+    ("管理費", Some(ItemKind::ManagementFee)),
+    ("共益費", Some(ItemKind::ManagementFee)),
+    ("敷金", Some(ItemKind::Deposit)),
+    ("保証金", Some(ItemKind::Deposit)),
+    ("礼金", Some(ItemKind::KeyMoney)),
+    ("敷引", Some(ItemKind::KeyMoney)),
+    ("仲介手数料", Some(ItemKind::BrokerageFee)),
+    ("フリーレント", Some(ItemKind::FreeRentPeriod)),
+    ("保証料", Some(ItemKind::GuaranteeFee)),
+    ("保証会社", Some(ItemKind::GuaranteeFee)),
+    ("24時間サポート", Some(ItemKind::SupportFee)),
+    ("安心サポート", Some(ItemKind::SupportFee)),
+    ("町内会費", Some(ItemKind::AssociationMembershipFee)),
+    ("町会費", Some(ItemKind::AssociationMembershipFee)),
+    ("事務手数料", Some(ItemKind::GuaranteeAdministrativeFee)),
+    ("保証委託", Some(ItemKind::GuaranteeAdministrativeFee)),
+    ("火災保険", Some(ItemKind::InsuranceFee)),
+    ("保険料", Some(ItemKind::InsuranceFee)),
+    ("駐輪場", Some(ItemKind::BicycleSpaceFee)),
+    ("バイク置場", Some(ItemKind::BicycleSpaceFee)),
+    ("駐車場", Some(ItemKind::CarSpaceFee)),
+    ("鍵交換", Some(ItemKind::KeyChangeFee)),
+    ("クリーニング", Some(ItemKind::CleaningFee)),
+    ("清掃費", Some(ItemKind::CleaningFee)),
+    ("更新料", Some(ItemKind::ContractUpdateFee)),
+    ("再契約料", Some(ItemKind::RecontractFee)),
+    ("再契約手数料", Some(ItemKind::RecontractFee)),
+    ("契約期間", Some(ItemKind::ContractPeriod)),
+];
+
+/// Words meaning the item is not charged.
+const NONE_WORDS: &[&str] = &["なし", "無し", "無料", "不要"];
+/// Mean the item is not charged when they are the whole value, and join the ends of a range otherwise.
+const DASHES: &[&str] = &["-", "－", "―"];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Amount {
+    Yen(i32),
+    Months(f64),
+    Percent(f64),
+    Years(f64),
+    Nothing,
+}
+
+/// Items read from pasted listing text.
+#[derive(Debug)]
+pub struct ListingImport {
+    pub elements: RentElements,
+    pub recognized: Vec<ItemKind>,
+}
+
+impl ListingImport {
+    /// Items left at the values they had before the import.
+    pub fn defaulted(&self) -> Vec<ItemKind> {
+        ItemKind::iter().filter(|k| !self.recognized.contains(k)).collect()
+    }
+}
+
+/// Positions of keywords in `text`, without overlaps.
+fn find_keywords(text: &str) -> Vec<(usize, usize, Option<ItemKind>)> {
+    let mut found = vec![];
+    let mut i = 0;
+    while i < text.len() {
+        let hit = KEYWORDS
+            .iter()
+            .filter(|(word, _)| text[i..].starts_with(word))
+            .max_by_key(|(word, _)| word.len());
+        match hit {
+            Some((word, kind)) => {
+                found.push((i, i + word.len(), *kind));
+                i += word.len();
+            }
+            None => i += text[i..].chars().next().map_or(1, |c| c.len_utf8()),
+        }
+    }
+    found
+}
+
+/// Amounts written in `segment`, such as `8.5万円`, `1ヶ月`, `50%` or `なし`.
+fn find_amounts(segment: &str) -> Vec<Amount> {
+    if DASHES.contains(&segment.trim_matches(|c: char| c.is_whitespace() || c == ':' || c == '：')) {
+        return vec![Amount::Nothing];
+    }
+    let mut amounts = vec![];
+    let mut rest = segment;
+    loop {
+        let start = rest.find(|c: char| c.is_ascii_digit() || c == '¥');
+        let none_at = NONE_WORDS.iter().filter_map(|w| rest.find(w)).min();
+        if let Some(n) = none_at {
+            if start.is_none_or(|s| n < s) {
+                amounts.push(Amount::Nothing);
+            }
+        }
+        let start = match start {
+            Some(s) => s,
+            None => return amounts,
+        };
+
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ',' || c == '¥' || c == '万' || c == '千'))
+            .unwrap_or(rest.len());
+        let (number, suffix) = rest.split_at(end);
+        // a range such as `1-2ヶ月` counts at its upper end
+        let upper = DASHES.iter()
+            .find_map(|d| suffix.strip_prefix(d))
+            .filter(|upper| upper.starts_with(|c: char| c.is_ascii_digit()));
+        if let Some(upper) = upper {
+            rest = upper;
+            continue;
+        }
+        let plain = || number.replace(',', "").trim_start_matches('¥').parse::<f64>().ok();
+
+        let amount = if ["ヶ月", "ヵ月", "か月", "カ月", "ケ月", "箇月"].iter().any(|s| suffix.starts_with(s)) {
+            plain().map(Amount::Months)
+        } else if suffix.starts_with('%') {
+            plain().map(Amount::Percent)
+        } else if suffix.starts_with('年') {
+            plain().map(Amount::Years)
+        } else {
+            parse_yen(number).map(Amount::Yen)
+        };
+        amounts.extend(amount);
+        rest = suffix;
+    }
+}
+
+/// Reads as many items as possible from text copied from a listing page, e.g.
+/// `賃料 8.5万円 管理費 5,000円 礼金1ヶ月 保証会社利用必須 初回50%`.
+///
+/// Months of rent and percentages are converted with the rent in the text (or in `base`).
+/// Guarantee fee percentages are of the rent plus the management fee, as is usual.
+pub fn parse_listing(text: &str, base: &RentElements) -> ListingImport {
+    let text: String = text.chars().map(to_half_width).collect();
+    let keywords = find_keywords(&text);
+
+    let mut amounts: Vec<(ItemKind, Vec<Amount>)> = vec![];
+    for (i, (_, end, kind)) in keywords.iter().enumerate() {
+        let next = keywords.get(i + 1).map_or(text.len(), |k| k.0);
+        let found = find_amounts(&text[*end..next]);
+        if let Some(kind) = kind {
+            // the first mention wins
+            if !found.is_empty() && !amounts.iter().any(|(k, _)| k == kind) {
+                amounts.push((*kind, found));
+            }
+        }
+    }
+
+    let mut elements = base.clone();
+    let mut recognized = vec![];
+
+    // the rent comes first, since other items may be relative to it
+    amounts.sort_by_key(|(k, _)| match k {
+        ItemKind::Rent => 0,
+        ItemKind::ManagementFee => 1,
+        _ => 2,
+    });
+    for (kind, found) in amounts {
+        let rent = elements.rent.value as f64;
+        let total_rent = rent + elements.management_fee.value as f64;
+        // the first amount that makes sense for the item, e.g. `15,000円` in `2年 15,000円`
+        let value = found.into_iter().find_map(|amount| match (kind.is_yen(), amount) {
+            (_, Amount::Nothing) => Some(0),
+            (true, Amount::Yen(v)) => Some(v),
+            (true, Amount::Months(m)) => Some((rent * m).round() as i32),
+            (true, Amount::Percent(p)) if kind == ItemKind::GuaranteeFee => Some((total_rent * p / 100.0).round() as i32),
+            (true, Amount::Percent(p)) => Some((rent * p / 100.0).round() as i32),
+            (false, Amount::Months(m)) => Some(m.round() as i32),
+            (false, Amount::Years(y)) => Some((y * 12.0).round() as i32),
+            _ => None,
+        });
+        if let Some(value) = value {
+            *elements.item_mut(kind) = RentItem::new(value, kind.units()[0]);
+            recognized.push(kind);
+        }
+    }
+
+    // a monthly guarantee fee is written as a percentage per month
+    if recognized.contains(&ItemKind::GuaranteeFee) && text.contains("月額保証") {
+        elements.guarantee_fee.unit = UnitId::MonthlyYen;
+    }
+
+    recognized.sort_by_key(|k| ItemKind::iter().position(|x| x == *k));
+    ListingImport { elements, recognized }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::RentElements;
+    use crate::constants::ItemKind;
+    use crate::listing::parse_listing;
+
+    #[test]
+    fn typical_listing() {
+        let r = parse_listing(
+            "賃料 8.5万円 管理費 5,000円 敷金1ヶ月 礼金1ヶ月 保証会社利用必須 初回50%",
+            &RentElements::default(),
+        );
+        assert_eq!(r.elements.rent.value, 85000);
+        assert_eq!(r.elements.management_fee.value, 5000);
+        assert_eq!(r.elements.deposit.value, 85000);
+        assert_eq!(r.elements.key_money.value, 85000);
+        assert_eq!(r.elements.guarantee_fee.value, 45000);
+        assert_eq!(
+            r.recognized,
+            vec![ItemKind::Rent, ItemKind::ManagementFee, ItemKind::Deposit, ItemKind::KeyMoney, ItemKind::GuaranteeFee]
+        );
+        assert!(r.defaulted().contains(&ItemKind::BrokerageFee));
+        assert_eq!(r.elements.brokerage_fee.value, RentElements::default().brokerage_fee.value);
+    }
+
+    #[test]
+    fn rent_after_relative_items() {
+        let r = parse_listing("礼金：２ヶ月\n家賃：６万円", &RentElements::default());
+        assert_eq!(r.elements.key_money.value, 120000);
+    }
+
+    #[test]
+    fn none_and_periods() {
+        let r = parse_listing(
+            "賃料 70000円 礼金なし 仲介手数料 無料 フリーレント1ヶ月 契約期間 2年 更新料 1ヶ月 火災保険 2年 15,000円",
+            &RentElements::default(),
+        );
+        assert_eq!(r.elements.key_money.value, 0);
+        assert_eq!(r.elements.brokerage_fee.value, 0);
+        assert_eq!(r.elements.free_rent_period.value, 1);
+        assert_eq!(r.elements.contract_period.value, 24);
+        assert_eq!(r.elements.contract_update_fee.value, 70000);
+        assert_eq!(r.elements.insurance_fee.value, 15000);
+    }
+
+    #[test]
+    fn dashes() {
+        let r = parse_listing("賃料 70000円 礼金 1-2ヶ月 敷金 - 仲介手数料：－ 更新料 50,000-60,000円", &RentElements::default());
+        assert_eq!(r.elements.key_money.value, 140000);
+        assert_eq!(r.elements.deposit.value, 0);
+        assert_eq!(r.elements.brokerage_fee.value, 0);
+        assert_eq!(r.elements.contract_update_fee.value, 60000);
+    }
+
+    #[test]
+    fn nothing_recognized() {
+        let r = parse_listing("駅徒歩5分 南向き", &RentElements::default());
+        assert!(r.recognized.is_empty());
+    }
+}
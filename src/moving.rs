@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::DAYS_PER_MONTH;
+use crate::constants::MovingKind;
+use crate::date::{Date, prorate};
+
+/// The apartment being left, whose rent keeps running after moving into the new one.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct CurrentHome {
+    /// Rent and management fee a month. 0 when there is no apartment to leave.
+    pub rent: i32,
+    /// Months of notice (解約予告) the lease requires.
+    pub notice_months: i32,
+    /// Last day of the old lease, if already planned.
+    pub move_out: Option<Date>,
+    /// Count the overlap in the new property's total instead of as a moving cost.
+    pub in_total: bool,
+}
+
+impl CurrentHome {
+    /// Days both rents are paid, from `move_in` to the end of the old lease.
+    /// Without dates, notice is assumed to be given when the new lease starts.
+    pub fn overlap_days(&self, move_in: Option<Date>) -> i32 {
+        if self.rent <= 0 {
+            return 0;
+        }
+        match (move_in, self.move_out) {
+            (Some(i), Some(o)) => (o.days_since(i) + 1).max(0),
+            (Some(i), None) => i.add_months(self.notice_months).days_since(i),
+            (None, _) => self.notice_months.max(0) * DAYS_PER_MONTH,
+        }
+    }
+
+    /// Rent paid for the old apartment while living in the new one, by the day.
+    pub fn overlap_cost(&self, move_in: Option<Date>) -> i32 {
+        prorate(self.rent.max(0), self.overlap_days(move_in), DAYS_PER_MONTH)
+    }
+
+    /// Latest day to give notice for the lease to end on `move_out`.
+    pub fn notice_by(&self) -> Option<Date> {
+        self.move_out.map(|o| o.next_day().add_months(-self.notice_months).prev_day())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MovingItem {
+    pub kind: MovingKind,
+    pub value: i32,
+}
+
+/// Costs of moving in besides the property's own fees.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct MovingCosts {
+    pub items: Vec<MovingItem>,
+    /// Spread over the lease in the effective rent. Otherwise they are only shown next to it.
+    pub in_total: bool,
+}
+
+impl Default for MovingCosts {
+    fn default() -> Self {
+        MovingCosts { items: vec![], in_total: true }
+    }
+}
+
+impl MovingCosts {
+    pub fn total(&self) -> i32 {
+        self.items.iter().map(|i| i.value).sum()
+    }
+
+    pub fn value(&self, kind: MovingKind) -> i32 {
+        self.items.iter().filter(|i| i.kind == kind).map(|i| i.value).sum()
+    }
+
+    /// Replaces the amount of `kind`, removing it when 0.
+    pub fn set(&mut self, kind: MovingKind, value: i32) {
+        self.items.retain(|i| i.kind != kind);
+        if value != 0 {
+            self.items.push(MovingItem { kind, value });
+        }
+    }
+
+    /// The amounts added to the property's total.
+    pub fn counted(&self) -> i32 {
+        if self.in_total { self.total() } else { 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::MovingKind;
+    use crate::date::Date;
+    use crate::moving::{CurrentHome, MovingCosts};
+
+    #[test]
+    fn overlap() {
+        let home = CurrentHome { rent: 90000, notice_months: 1, ..Default::default() };
+        assert_eq!(home.overlap_days(None), 30);
+        assert_eq!(home.overlap_cost(None), 90000);
+        let move_in = Date::new(2024, 4, 21);
+        assert_eq!(home.overlap_days(move_in), 30);
+
+        let home = CurrentHome { move_out: Date::new(2024, 4, 30), ..home };
+        assert_eq!(home.overlap_days(move_in), 10);
+        assert_eq!(home.overlap_cost(move_in), 30000);
+        assert_eq!(home.overlap_days(Date::new(2024, 5, 1)), 0);
+        assert_eq!(home.overlap_days(Date::new(2024, 5, 10)), 0);
+        assert_eq!(home.notice_by(), Date::new(2024, 3, 31));
+
+        assert_eq!(CurrentHome::default().overlap_cost(move_in), 0);
+    }
+
+    #[test]
+    fn moving_costs() {
+        let mut costs = MovingCosts::default();
+        costs.set(MovingKind::Movers, 50000);
+        costs.set(MovingKind::Curtains, 15000);
+        costs.set(MovingKind::Movers, 60000);
+        assert_eq!(costs.value(MovingKind::Movers), 60000);
+        assert_eq!(costs.total(), 75000);
+        assert_eq!(costs.counted(), 75000);
+        costs.set(MovingKind::Curtains, 0);
+        assert_eq!(costs.items.len(), 1);
+        assert_eq!(MovingCosts { in_total: false, ..costs }.counted(), 0);
+    }
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+
+use crate::CalcError;
+use crate::calculator::RentElements;
+use crate::decision::lease_totals;
+
+/// Changes of the mortgage rate, in percentage points, and of the resale value, in percent, tried for the sensitivity.
+const RATE_STEPS: [f64; 4] = [-1.0, -0.5, 0.5, 1.0];
+const RESALE_STEPS: [f64; 4] = [-20.0, -10.0, 10.0, 20.0];
+
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tenure {
+    Rent,
+    Buy,
+}
+
+/// Buying a home instead of renting the property.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Purchase {
+    pub price: i32,
+    pub down_payment: i32,
+    /// Annual mortgage rate in percent, compounded monthly.
+    pub mortgage_rate: f64,
+    pub mortgage_years: i32,
+    /// 管理費 a month.
+    pub management_fee: i32,
+    /// 修繕積立金 a month.
+    pub repair_reserve: i32,
+    /// 固定資産税 a year.
+    pub property_tax: i32,
+    /// Fees and taxes paid when buying (諸費用).
+    pub closing_costs: i32,
+    /// Expected sale price at the end of the horizon, after the selling costs. The value declines linearly to it.
+    pub resale_value: i32,
+    /// Years both are compared over. 0 turns the comparison off.
+    pub horizon_years: i32,
+}
+
+/// Costs from now to the end of a year, as if leaving then: moving out of the rented property,
+/// or selling the home.
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct YearCost {
+    pub year: i32,
+    pub rent: i32,
+    /// What was paid, less what selling would bring after paying the loan back.
+    pub buy: i32,
+}
+
+/// The buying cost with a changed mortgage rate or resale value.
+#[derive(Serialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Variant {
+    pub mortgage_rate: f64,
+    pub resale_value: i32,
+    pub buy_total: i32,
+    pub crossover_year: Option<i32>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RentVsBuy {
+    pub horizon_years: i32,
+    pub monthly_payment: i32,
+    pub rent_total: i32,
+    pub buy_total: i32,
+    pub cheaper: Tenure,
+    /// Cumulative costs at the end of each year.
+    pub years: Vec<YearCost>,
+    /// First year after which buying has cost less, if any.
+    pub crossover_year: Option<i32>,
+    pub by_rate: Vec<Variant>,
+    pub by_resale: Vec<Variant>,
+}
+
+impl Purchase {
+    fn principal(&self) -> f64 {
+        (self.price - self.down_payment).max(0) as f64
+    }
+
+    fn months(&self) -> i32 {
+        self.mortgage_years.max(0) * 12
+    }
+
+    /// Level monthly payment of the mortgage.
+    pub fn monthly_payment(&self) -> f64 {
+        let (p, n, r) = (self.principal(), self.months(), self.mortgage_rate / 100.0 / 12.0);
+        if n <= 0 {
+            0.0
+        } else if r == 0.0 {
+            p / n as f64
+        } else {
+            p * r / (1.0 - (1.0 + r).powi(-n))
+        }
+    }
+
+    /// What is left to pay back after `month` payments.
+    pub fn balance(&self, month: i32) -> f64 {
+        let (p, n, r) = (self.principal(), self.months(), self.mortgage_rate / 100.0 / 12.0);
+        if month >= n {
+            0.0
+        } else if r == 0.0 {
+            p * (n - month) as f64 / n as f64
+        } else {
+            let growth = (1.0 + r).powi(month);
+            p * growth - self.monthly_payment() * (growth - 1.0) / r
+        }
+    }
+
+    /// Value of the home after `month` months of the horizon.
+    fn value(&self, month: i32) -> f64 {
+        let horizon = (self.horizon_years * 12).max(1);
+        self.price as f64 + (self.resale_value - self.price) as f64 * month as f64 / horizon as f64
+    }
+
+    /// Cost of buying when selling at the end of each year of the horizon.
+    pub fn cumulative_costs(&self) -> Vec<i32> {
+        let payment = self.monthly_payment();
+        let mut paid = (self.down_payment + self.closing_costs) as f64;
+        let mut costs = vec![];
+        for month in 0..self.horizon_years * 12 {
+            if month % 12 == 0 {
+                paid += self.property_tax as f64;
+            }
+            if month < self.months() {
+                paid += payment;
+            }
+            paid += (self.management_fee + self.repair_reserve) as f64;
+            if month % 12 == 11 {
+                let equity = self.value(month + 1) - self.balance(month + 1);
+                costs.push((paid - equity).round() as i32);
+            }
+        }
+        costs
+    }
+}
+
+/// First year in which `buy` is at most `rent`, staying so to the end.
+fn crossover(rent: &[i32], buy: &[i32]) -> Option<i32> {
+    let last_worse = rent.iter().zip(buy).rposition(|(r, b)| b > r);
+    match last_worse {
+        Some(y) if y + 1 == rent.len() => None,
+        Some(y) => Some(y as i32 + 2),
+        None => Some(1),
+    }
+}
+
+/// Compares renting the property of `e`, renewing it over the horizon, with buying as `e.purchase`.
+pub fn rent_vs_buy(e: &RentElements) -> Result<RentVsBuy, CalcError> {
+    let purchase = e.purchase;
+    let horizon = purchase.horizon_years;
+    if horizon <= 0 {
+        return Err(CalcError::HorizonTooShort);
+    }
+    // like the buying costs, each year counts the costs of leaving then
+    let totals = lease_totals(e, horizon * 12);
+    let rent: Vec<i32> = (1..=horizon).map(|y| totals[y as usize * 12]).collect();
+    let buy = purchase.cumulative_costs();
+
+    let variant = |p: Purchase| {
+        let costs = p.cumulative_costs();
+        Variant {
+            mortgage_rate: p.mortgage_rate,
+            resale_value: p.resale_value,
+            buy_total: costs[costs.len() - 1],
+            crossover_year: crossover(&rent, &costs),
+        }
+    };
+    let by_rate = RATE_STEPS
+        .iter()
+        .map(|d| purchase.mortgage_rate + d)
+        .filter(|r| *r >= 0.0)
+        .map(|mortgage_rate| variant(Purchase { mortgage_rate, ..purchase }))
+        .collect();
+    let by_resale = RESALE_STEPS
+        .iter()
+        .map(|d| (purchase.resale_value as f64 * (1.0 + d / 100.0)).round() as i32)
+        .map(|resale_value| variant(Purchase { resale_value, ..purchase }))
+        .collect();
+
+    let (rent_total, buy_total) = (rent[rent.len() - 1], buy[buy.len() - 1]);
+    Ok(RentVsBuy {
+        horizon_years: horizon,
+        monthly_payment: purchase.monthly_payment().round() as i32,
+        rent_total,
+        buy_total,
+        cheaper: if buy_total < rent_total { Tenure::Buy } else { Tenure::Rent },
+        years: (1..=horizon)
+            .zip(rent.iter().zip(&buy))
+            .map(|(year, (rent, buy))| YearCost { year, rent: *rent, buy: *buy })
+            .collect(),
+        crossover_year: crossover(&rent, &buy),
+        by_rate,
+        by_resale,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentCalculator, RentElements, RentItem};
+    use crate::constants::UnitId;
+    use crate::decision::candidate;
+    use crate::purchase::{Purchase, crossover, rent_vs_buy};
+
+    #[test]
+    fn mortgage() {
+        let p = Purchase { price: 1200000, mortgage_years: 1, ..Default::default() };
+        assert_eq!(p.monthly_payment(), 100000.0);
+        assert_eq!(p.balance(6), 600000.0);
+        let p = Purchase { mortgage_rate: 1.2, mortgage_years: 35, price: 40000000, ..Default::default() };
+        assert!(p.monthly_payment() * 420.0 > 40000000.0);
+        assert!((p.balance(0) - 40000000.0).abs() < 1e-3);
+        assert!(p.balance(420).abs() < 1e-3);
+    }
+
+    #[test]
+    fn crossover_year() {
+        assert_eq!(crossover(&[10, 20, 30], &[15, 18, 25]), Some(2));
+        assert_eq!(crossover(&[10, 20, 30], &[5, 25, 25]), Some(3));
+        assert_eq!(crossover(&[10, 20, 30], &[15, 25, 35]), None);
+        assert_eq!(crossover(&[10, 20, 30], &[5, 15, 25]), Some(1));
+    }
+
+    #[test]
+    fn compare() {
+        let e = RentElements {
+            purchase: Purchase {
+                price: 30000000,
+                down_payment: 3000000,
+                mortgage_rate: 1.0,
+                mortgage_years: 35,
+                management_fee: 10000,
+                repair_reserve: 10000,
+                property_tax: 100000,
+                closing_costs: 2000000,
+                resale_value: 25000000,
+                horizon_years: 10,
+            },
+            ..Default::default()
+        };
+        let r = rent_vs_buy(&e).unwrap();
+        assert_eq!(r.years.len(), 10);
+        assert_eq!(r.rent_total, r.years[9].rent);
+        assert!(r.years.windows(2).all(|w| w[0].rent < w[1].rent));
+        // selling after a year loses the closing costs and the decline in value
+        assert!(r.years[0].buy > 2000000 + 500000);
+        assert_eq!(r.by_rate.len(), 4);
+        assert!(r.by_rate[0].buy_total < r.buy_total && r.buy_total < r.by_rate[3].buy_total);
+        assert!(r.by_resale[0].buy_total > r.buy_total && r.buy_total > r.by_resale[3].buy_total);
+
+        let e = RentElements { purchase: Purchase { horizon_years: 0, ..e.purchase }, ..e };
+        assert!(rent_vs_buy(&e).is_err());
+    }
+
+    #[test]
+    fn move_out_costs_every_year() {
+        let e = RentElements {
+            cleaning_fee: RentItem::new(300000, UnitId::OneShotYen),
+            ..Default::default()
+        };
+        let rent_first_year = RentCalculator {}.calc(candidate(&e, 12)).unwrap().total;
+        // buying costs the same each month as renting, and 100,000 yen less than leaving the rental after a year
+        let e = RentElements {
+            purchase: Purchase {
+                price: 10000000,
+                down_payment: 10000000,
+                management_fee: 52000,
+                closing_costs: rent_first_year - 100000 - 12 * 52000,
+                resale_value: 10000000,
+                horizon_years: 3,
+                ..Default::default()
+            },
+            ..e
+        };
+        let r = rent_vs_buy(&e).unwrap();
+        for (y, year) in r.years.iter().enumerate() {
+            let total = RentCalculator {}.calc(candidate(&e, 12 * (y as i32 + 1))).unwrap().total;
+            assert_eq!(year.rent, total);
+        }
+        assert_eq!(r.years[0].rent - r.years[0].buy, 100000);
+        assert_eq!(r.crossover_year, Some(1));
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+
+use wasm_bindgen::prelude::*;
+
+use crate::calculator::RentResult;
+
+/// Figures results can be ranked by, cheapest first.
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RankKey {
+    Total,
+    Monthly,
+    LevelMonthly,
+    LivingMonthly,
+    PerSquareMeter,
+    PerJo,
+    PerPerson,
+}
+
+impl RentResult {
+    /// `None` when the figure is unknown, such as the rent per ㎡ without an area.
+    pub fn rank_value(&self, key: RankKey) -> Option<i32> {
+        match key {
+            RankKey::Total => Some(self.total),
+            RankKey::Monthly => Some(self.monthly),
+            RankKey::LevelMonthly => Some(self.level_monthly),
+            RankKey::LivingMonthly => Some(self.living_monthly),
+            RankKey::PerSquareMeter => self.per_square_meter,
+            RankKey::PerJo => self.per_jo,
+            RankKey::PerPerson => Some(self.per_person),
+        }
+    }
+}
+
+/// Indices of `results` from the cheapest by `key`, the unknown ones last.
+pub fn rank(results: &[RentResult], key: RankKey) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by(|a, b| match (results[*a].rank_value(key), results[*b].rank_value(key)) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::area::Area;
+    use crate::calculator::{RentCalculator, RentElements, RentItem};
+    use crate::constants::UnitId;
+    use crate::ranking::{RankKey, rank};
+
+    #[test]
+    fn rank_by_key() {
+        let small = RentElements { area: Area { square_meters: 25.0, jo: 0.0 }, ..Default::default() };
+        let large = RentElements {
+            rent: RentItem::new(70000, UnitId::MonthlyYen),
+            area: Area { square_meters: 40.0, jo: 10.0 },
+            ..Default::default()
+        };
+        let unknown = RentElements { rent: RentItem::new(40000, UnitId::MonthlyYen), ..Default::default() };
+        let results: Vec<_> = vec![small, large, unknown]
+            .into_iter()
+            .map(|e| RentCalculator {}.calc(e).unwrap())
+            .collect();
+        assert_eq!(rank(&results, RankKey::Monthly), vec![2, 0, 1]);
+        assert_eq!(rank(&results, RankKey::PerSquareMeter), vec![1, 0, 2]);
+        assert_eq!(rank(&results, RankKey::PerJo), vec![1, 0, 2]);
+    }
+}
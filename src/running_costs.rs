@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::constants::RunningKind;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RunningItem {
+    pub kind: RunningKind,
+    /// Estimate a month.
+    pub value: i32,
+    /// Already paid with the rent, like free internet or a flat water charge in the management fee.
+    pub included: bool,
+}
+
+/// Monthly costs of living in the property besides the rent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct RunningCosts {
+    pub items: Vec<RunningItem>,
+}
+
+impl RunningCosts {
+    pub fn item(&self, kind: RunningKind) -> Option<RunningItem> {
+        self.items.iter().copied().find(|i| i.kind == kind)
+    }
+
+    /// Replaces the estimate of `kind`, removing it when 0 and not included.
+    pub fn set(&mut self, kind: RunningKind, value: i32, included: bool) {
+        self.items.retain(|i| i.kind != kind);
+        if value != 0 || included {
+            self.items.push(RunningItem { kind, value, included });
+        }
+    }
+
+    /// What is paid a month on top of the rent.
+    pub fn monthly(&self) -> i32 {
+        self.items.iter().filter(|i| !i.included).map(|i| i.value).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::RunningKind;
+    use crate::running_costs::RunningCosts;
+
+    #[test]
+    fn monthly() {
+        let mut costs = RunningCosts::default();
+        costs.set(RunningKind::Electricity, 6000, false);
+        costs.set(RunningKind::Water, 2000, true);
+        costs.set(RunningKind::Internet, 5000, false);
+        assert_eq!(costs.monthly(), 11000);
+        costs.set(RunningKind::Internet, 0, true);
+        assert_eq!(costs.monthly(), 6000);
+        assert_eq!(costs.item(RunningKind::Internet).map(|i| i.included), Some(true));
+        costs.set(RunningKind::Electricity, 0, false);
+        assert_eq!(costs.items.len(), 2);
+    }
+}
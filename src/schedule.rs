@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, HtmlInputElement, HtmlSelectElement};
+
+use crate::constants::{InputId, ItemKind};
+use crate::yen::parse_yen;
+
+/// How an amount changes at a renewal.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Change {
+    /// The new amount in yen.
+    Set(i32),
+    /// Raise (or lower, if negative) by this percentage.
+    Percent(f64),
+}
+
+/// A change of `item` taking effect at the `renewal`-th renewal (1 = the first one)
+/// and staying in force afterwards.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct TermChange {
+    pub renewal: i32,
+    pub item: ItemKind,
+    pub change: Change,
+}
+
+/// Months lived in each contract term. Always has at least one term.
+pub fn term_lengths(lease_period: i32, contract_period: i32) -> Vec<i32> {
+    if lease_period <= 0 || contract_period <= 0 {
+        return vec![lease_period.max(0)];
+    }
+    let mut terms = vec![];
+    let mut rest = lease_period;
+    while rest > 0 {
+        terms.push(rest.min(contract_period));
+        rest -= contract_period;
+    }
+    terms
+}
+
+/// Value of `item` in force during each of `terms` terms, starting from `base`.
+pub fn term_values(base: i32, item: ItemKind, schedule: &[TermChange], terms: usize) -> Vec<i32> {
+    let mut value = base;
+    (0..terms)
+        .map(|t| {
+            for c in schedule.iter().filter(|c| c.item == item && c.renewal == t as i32) {
+                value = match c.change {
+                    Change::Set(v) => v,
+                    Change::Percent(p) => (value as f64 * (1.0 + p / 100.0)).round() as i32,
+                };
+            }
+            value
+        })
+        .collect()
+}
+
+/// Reads the rows rendered by `form_items::ScheduleForm`.
+/// Rows with an empty or unreadable value are skipped.
+pub fn read_schedule(document: &Document) -> Option<Vec<TermChange>> {
+    let container = document.get_element_by_id(&InputId::ScheduleRows.to_string())?;
+    let mut changes = vec![];
+    let mut row = container.first_element_child();
+    while let Some(r) = row {
+        changes.extend(read_row(&r));
+        row = r.next_element_sibling();
+    }
+    Some(changes)
+}
+
+fn read_row(row: &Element) -> Option<TermChange> {
+    let field = |name: &str| -> Option<String> {
+        let e = row.query_selector(&format!("[name={}]", name)).ok()??;
+        e.dyn_ref::<HtmlInputElement>().map(|i| i.value())
+            .or_else(|| e.dyn_ref::<HtmlSelectElement>().map(|s| s.value()))
+    };
+    let item = field("item")?.parse::<i32>().ok()?;
+    let item = ItemKind::iter().find(|k| *k as i32 == item && k.is_yen())?;
+    let renewal = field("renewal")?.parse::<i32>().ok().filter(|r| *r >= 1)?;
+    let value = field("value")?;
+    let change = match field("kind")?.as_str() {
+        "percent" => Change::Percent(value.trim().parse::<f64>().ok()?),
+        _ => Change::Set(parse_yen(&value)?),
+    };
+    Some(TermChange { renewal, item, change })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::ItemKind;
+    use crate::schedule::{Change, TermChange, term_lengths, term_values};
+
+    #[test]
+    fn lengths() {
+        assert_eq!(term_lengths(24, 24), vec![24]);
+        assert_eq!(term_lengths(25, 24), vec![24, 1]);
+        assert_eq!(term_lengths(60, 24), vec![24, 24, 12]);
+        assert_eq!(term_lengths(0, 24), vec![0]);
+    }
+
+    #[test]
+    fn values() {
+        let schedule = vec![
+            TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Percent(10.0) },
+            TermChange { renewal: 2, item: ItemKind::Rent, change: Change::Set(60000) },
+            TermChange { renewal: 1, item: ItemKind::ManagementFee, change: Change::Set(3000) },
+        ];
+        assert_eq!(term_values(50000, ItemKind::Rent, &schedule, 4), vec![50000, 55000, 60000, 60000]);
+        assert_eq!(term_values(2000, ItemKind::ManagementFee, &schedule, 2), vec![2000, 3000]);
+        assert_eq!(term_values(5000, ItemKind::KeyMoney, &schedule, 2), vec![5000, 5000]);
+    }
+}
@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, HtmlInputElement, HtmlSelectElement};
+
+use crate::calculator::{DAYS_PER_MONTH, RentElements};
+use crate::cash_flow::{extra_costs, item_flows};
+use crate::constants::{InputId, ItemKind, UnitId};
+use crate::date::prorate;
+use crate::schedule::term_lengths;
+use crate::yen::parse_yen;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Share {
+    /// Part of what the fixed amounts leave, weighed against the other percentages of the item.
+    Percent(f64),
+    /// Yen each time the item is charged, e.g. every month for the rent.
+    Fixed(i32),
+}
+
+/// How much of `item` `person` pays.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SplitRule {
+    pub item: ItemKind,
+    pub person: String,
+    pub share: Share,
+}
+
+/// People sharing the property and how they split the items.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Split {
+    /// Whatever the rules leave is split equally between them.
+    pub people: Vec<String>,
+    pub rules: Vec<SplitRule>,
+}
+
+/// What one person pays.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonShare {
+    pub name: String,
+    /// Paid when moving in, the deposit included.
+    pub upfront: i32,
+    /// Paid every month from the second month on.
+    pub monthly: i32,
+    /// Over the whole stay, without the deposit.
+    pub total: i32,
+    /// `total` per month, like `RentResult::monthly`.
+    pub effective: i32,
+}
+
+impl Split {
+    /// The people, including those only named in the rules.
+    pub fn names(&self) -> Vec<String> {
+        let mut names = self.people.clone();
+        for rule in &self.rules {
+            if !names.contains(&rule.person) {
+                names.push(rule.person.clone());
+            }
+        }
+        names
+    }
+
+    /// Part of `item` each of `names` pays, where `value` is what the item charges each time in yen.
+    /// A fixed share above the charge pays all of it.
+    fn fractions(&self, names: &[String], item: ItemKind, value: i32) -> Vec<f64> {
+        let mut fractions = vec![0.0; names.len()];
+        let rules: Vec<(usize, Share)> = self.rules
+            .iter()
+            .filter(|r| r.item == item)
+            .filter_map(|r| names.iter().position(|n| *n == r.person).map(|p| (p, r.share)))
+            .collect();
+
+        for (p, share) in &rules {
+            if let Share::Fixed(amount) = share {
+                fractions[*p] += if value > 0 { (*amount as f64 / value as f64).clamp(0.0, 1.0) } else { 0.0 };
+            }
+        }
+        let fixed: f64 = fractions.iter().sum();
+        if fixed > 1.0 {
+            fractions.iter_mut().for_each(|f| *f /= fixed);
+        }
+        let rest = (1.0 - fixed).max(0.0);
+
+        let percents: Vec<(usize, f64)> = rules
+            .iter()
+            .filter_map(|(p, share)| match share {
+                Share::Percent(percent) => Some((*p, percent.max(0.0))),
+                Share::Fixed(_) => None,
+            })
+            .collect();
+        let weight: f64 = percents.iter().map(|(_, w)| w).sum();
+        if weight > 0.0 {
+            for (p, w) in percents {
+                fractions[p] += rest * w / weight;
+            }
+        } else {
+            // the rest is shared by everyone
+            fractions.iter_mut().for_each(|f| *f += rest / names.len() as f64);
+        }
+        fractions
+    }
+}
+
+/// Yen `kind` charges each time, in each of the first `terms` terms. Percent units are of the term's rent.
+fn term_charges(e: &RentElements, kind: ItemKind, terms: usize) -> Vec<i32> {
+    let rents = e.term_values(ItemKind::Rent, terms);
+    e.term_values(kind, terms)
+        .into_iter()
+        .zip(rents)
+        .map(|(v, rent)| match e.item(kind).unit {
+            UnitId::UpdateRentPercent => rent * v / 100,
+            UnitId::DailyYen => v * DAYS_PER_MONTH,
+            UnitId::WeeklyYen => prorate(v, DAYS_PER_MONTH, 7),
+            _ => v,
+        })
+        .collect()
+}
+
+/// Paid every month for `kind`, from the second month on.
+fn monthly_amount(e: &RentElements, kind: ItemKind) -> i32 {
+    let lease_period = e.lease_months();
+    let month = if lease_period > 1 { 1 } else { 0 };
+    let components: i32 = e.components
+        .iter()
+        .filter(|c| c.item == kind)
+        .map(|c| match c.charge.unit {
+            UnitId::MonthlyYen => c.charge.value,
+            UnitId::DailyYen => c.charge.value * DAYS_PER_MONTH,
+            UnitId::WeeklyYen => prorate(c.charge.value, DAYS_PER_MONTH, 7),
+            _ => 0,
+        })
+        .sum();
+    e.month_amount(kind, month, lease_period, e.contract_period.get(0, 0)) + components
+}
+
+/// Splits the costs of `e` between the people of `e.split`. Empty when nobody is named.
+pub fn split_costs(e: &RentElements) -> Vec<PersonShare> {
+    let names = e.split.names();
+    if names.is_empty() {
+        return vec![];
+    }
+    // upfront, monthly and total of each person
+    let mut sums = vec![(0.0, 0.0, 0.0); names.len()];
+    let mut add = |fractions: Vec<f64>, upfront: f64, monthly: f64, total: f64| {
+        for (s, f) in sums.iter_mut().zip(fractions) {
+            s.0 += f * upfront;
+            s.1 += f * monthly;
+            s.2 += f * total;
+        }
+    };
+
+    // term of each month of the lease, and of move-out
+    let terms = term_lengths(e.lease_months(), e.contract_period.value);
+    let mut term_of: Vec<usize> = terms.iter()
+        .enumerate()
+        .flat_map(|(t, months)| vec![t; *months as usize])
+        .collect();
+    term_of.push(terms.len() - 1);
+    let second_month = term_of[1.min(term_of.len() - 1)];
+
+    for kind in e.cost_items() {
+        let flows = item_flows(e, kind);
+        // shares are fixed per charge, so each term has its own fractions
+        let fractions: Vec<Vec<f64>> = term_charges(e, kind, terms.len())
+            .into_iter()
+            .map(|charge| e.split.fractions(&names, kind, charge))
+            .collect();
+        for (month, flow) in flows.iter().enumerate() {
+            let upfront = if month == 0 { *flow as f64 } else { 0.0 };
+            add(fractions[term_of[month]].clone(), upfront, 0.0, *flow as f64);
+        }
+        add(fractions[second_month].clone(), 0.0, monthly_amount(e, kind) as f64, 0.0);
+    }
+    let deposit = e.deposit.value as f64;
+    add(e.split.fractions(&names, ItemKind::Deposit, e.deposit.value), deposit, 0.0, 0.0);
+    let extra = extra_costs(e) as f64;
+    add(vec![1.0 / names.len() as f64; names.len()], extra, 0.0, extra);
+
+    names
+        .into_iter()
+        .zip(sums)
+        .map(|(name, (upfront, monthly, total))| PersonShare {
+            name,
+            upfront: upfront.round() as i32,
+            monthly: monthly.round() as i32,
+            total: total.round() as i32,
+            effective: e.per_month(total.round() as i32),
+        })
+        .collect()
+}
+
+/// Reads the names and the rule rows, or `None` if they are not on the form.
+pub fn read_split(document: &Document) -> Option<Split> {
+    let people = document
+        .get_element_by_id(&InputId::SplitPeople.to_string())?
+        .dyn_into::<HtmlInputElement>()
+        .ok()?
+        .value();
+    let people = people
+        .split([',', '、'])
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .collect();
+
+    let container = document.get_element_by_id(&InputId::SplitRows.to_string())?;
+    let mut rules = vec![];
+    let mut row = container.first_element_child();
+    while let Some(r) = row {
+        rules.extend(read_row(&r));
+        row = r.next_element_sibling();
+    }
+    Some(Split { people, rules })
+}
+
+fn read_row(row: &Element) -> Option<SplitRule> {
+    let field = |name: &str| -> Option<String> {
+        let e = row.query_selector(&format!("[name={}]", name)).ok()??;
+        e.dyn_ref::<HtmlInputElement>().map(|i| i.value())
+            .or_else(|| e.dyn_ref::<HtmlSelectElement>().map(|s| s.value()))
+    };
+    let item = field("item")?.parse::<i32>().ok()?;
+    let item = ItemKind::iter().find(|k| *k as i32 == item && k.is_yen())?;
+    let person = field("person")?.trim().to_string();
+    if person.is_empty() {
+        return None;
+    }
+    let value = field("value")?;
+    let share = match field("kind")?.as_str() {
+        "percent" => Share::Percent(value.trim().parse::<f64>().ok()?),
+        _ => Share::Fixed(parse_yen(&value)?),
+    };
+    Some(SplitRule { item, person, share })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentCalculator, RentElements, RentItem};
+    use crate::constants::{ItemKind, UnitId};
+    use crate::schedule::{Change, TermChange};
+    use crate::split::{Share, Split, SplitRule, split_costs};
+
+    fn rule(item: ItemKind, person: &str, share: Share) -> SplitRule {
+        SplitRule { item, person: person.to_string(), share }
+    }
+
+    #[test]
+    fn split_equally() {
+        let e = RentElements {
+            split: Split { people: vec!["A".to_string(), "B".to_string()], rules: vec![] },
+            ..Default::default()
+        };
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        let shares = split_costs(&e);
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].total + shares[1].total, r.total);
+        assert_eq!(shares[0].monthly, (50000 + 2000) / 2);
+        assert_eq!(shares[0].effective, r.monthly / 2);
+    }
+
+    #[test]
+    fn split_unevenly() {
+        let e = RentElements {
+            car_space_fee: RentItem::new(20000, UnitId::MonthlyYen),
+            split: Split {
+                people: vec!["A".to_string(), "B".to_string()],
+                rules: vec![
+                    rule(ItemKind::Rent, "A", Share::Percent(60.0)),
+                    rule(ItemKind::Rent, "B", Share::Percent(40.0)),
+                    rule(ItemKind::CarSpaceFee, "B", Share::Percent(100.0)),
+                    rule(ItemKind::KeyMoney, "A", Share::Fixed(30000)),
+                ],
+            },
+            ..Default::default()
+        };
+        let shares = split_costs(&e);
+        assert_eq!(shares[0].monthly, 30000 + 1000);
+        assert_eq!(shares[1].monthly, 20000 + 1000 + 20000);
+        // A pays 30,000 of the key money, and the rest is shared
+        let key_money = 50000;
+        let upfront_a = 30000 + 1000 + 30000 + (key_money - 30000) / 2;
+        assert_eq!(shares[0].upfront - upfront_a, (50000 + 50000 + 10000 + 3000 + 5000) / 2);
+
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert!((shares[0].total + shares[1].total - r.total).abs() <= 1);
+    }
+
+    #[test]
+    fn fixed_share_of_percent_renewal_fee() {
+        let e = RentElements {
+            lease_period: RentItem::new(48, UnitId::Month),
+            contract_update_fee: RentItem::new(100, UnitId::UpdateRentPercent),
+            split: Split { people: vec!["A".to_string(), "B".to_string()], rules: vec![] },
+            ..Default::default()
+        };
+        let even = split_costs(&e);
+        let mut e = e;
+        e.split.rules = vec![rule(ItemKind::ContractUpdateFee, "A", Share::Fixed(30000))];
+        let shares = split_costs(&e);
+        // the renewal fee is a month of rent, 50,000 yen: A pays 30,000 and half the rest
+        assert_eq!(shares[0].total - even[0].total, 30000 + 10000 - 25000);
+        assert_eq!(shares[1].total - even[1].total, 10000 - 25000);
+    }
+
+    #[test]
+    fn fixed_share_of_scheduled_rent() {
+        let e = RentElements {
+            lease_period: RentItem::new(48, UnitId::Month),
+            schedule: vec![TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Set(60000) }],
+            split: Split {
+                people: vec!["A".to_string(), "B".to_string()],
+                rules: vec![rule(ItemKind::Rent, "A", Share::Fixed(40000))],
+            },
+            ..Default::default()
+        };
+        let shares = split_costs(&e);
+        assert_eq!(shares[0].monthly, 40000 + 5000 + 1000);
+        assert_eq!(shares[1].monthly, 5000 + 1000);
+        // after the renewal A still pays 40,000 of the 60,000
+        let rent_a = 24 * (40000 + 5000) + 24 * (40000 + 10000);
+        let rent_b = 24 * 5000 + 24 * 10000;
+        assert!((shares[0].total - shares[1].total - (rent_a - rent_b)).abs() <= 1);
+
+        let r = RentCalculator {}.calc(e).unwrap();
+        assert!((shares[0].total + shares[1].total - r.total).abs() <= 1);
+    }
+
+    #[test]
+    fn fixed_share_over_terms_of_different_lengths() {
+        // a first term of 24 months at 50,000 and a last one of 12 months at 60,000
+        let lease = |months: i32, rules: Vec<SplitRule>| RentElements {
+            lease_period: RentItem::new(months, UnitId::Month),
+            schedule: vec![TermChange { renewal: 1, item: ItemKind::Rent, change: Change::Set(60000) }],
+            split: Split { people: vec!["A".to_string(), "B".to_string()], rules },
+            ..Default::default()
+        };
+        let rules = vec![rule(ItemKind::Rent, "A", Share::Fixed(40000))];
+        // what each person pays of the rent over the lease, beyond an even split
+        let extra = |months: i32| -> Vec<i32> {
+            let even = split_costs(&lease(months, vec![]));
+            let shares = split_costs(&lease(months, rules.clone()));
+            shares.iter().zip(even).map(|(s, e)| s.total - e.total).collect()
+        };
+        let first_term = extra(24);
+        let both_terms = extra(36);
+        // A pays 40,000 and half of the rest each month: 45,000 of 50,000, then 50,000 of 60,000
+        assert_eq!(first_term, vec![24 * (45000 - 25000), 24 * (5000 - 25000)]);
+        let last_term: Vec<i32> = both_terms.iter().zip(&first_term).map(|(b, f)| b - f).collect();
+        assert_eq!(last_term, vec![12 * (50000 - 30000), 12 * (10000 - 30000)]);
+    }
+}
@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, HtmlInputElement};
+
+use crate::calculator::RentElements;
+use crate::cash_flow::item_flows;
+use crate::constants::{InputId, ItemKind};
+
+/// What the employer pays towards the property, as a housing allowance (住宅手当) or a corporate lease (借り上げ社宅).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Subsidy {
+    /// Paid every month of the stay.
+    pub allowance: i32,
+    /// Part of the rent paid every month, in percent.
+    pub rent_percent: f64,
+    /// Most the employer pays of the rent a month. 0 for no cap.
+    pub rent_cap: i32,
+    /// Items the employer pays in full, e.g. the key money and the brokerage fee.
+    pub paid_items: Vec<ItemKind>,
+    /// Nothing is paid when the rent and management fee are above this. 0 for no limit.
+    pub rent_limit: i32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsidyResult {
+    /// The rent and management fee are within the limit.
+    pub eligible: bool,
+    pub rent_limit: i32,
+    pub allowance_total: i32,
+    /// The employer's part of the rent over the stay.
+    pub rent_share_total: i32,
+    pub paid_items_total: i32,
+    /// Everything the employer pays over the stay.
+    pub employer_total: i32,
+    /// What the employee pays of the total.
+    pub net_total: i32,
+    /// `net_total` per month, the employee's effective rent.
+    pub net_monthly: i32,
+}
+
+impl Subsidy {
+    pub fn is_empty(&self) -> bool {
+        self.allowance == 0 && self.rent_percent == 0.0 && self.paid_items.is_empty()
+    }
+}
+
+/// Splits `total`, the total of `e`, between the employer and the employee. `None` without a subsidy.
+/// `advertised` is the rent and management fee checked against the limit.
+pub fn subsidize(e: &RentElements, total: i32, advertised: i32) -> Option<SubsidyResult> {
+    let subsidy = &e.subsidy;
+    if subsidy.is_empty() {
+        return None;
+    }
+    let eligible = subsidy.rent_limit <= 0 || advertised <= subsidy.rent_limit;
+    let (allowance_total, rent_share_total, paid_items_total) = if eligible {
+        let rent_share = item_flows(e, ItemKind::Rent)
+            .iter()
+            .take(e.lease_months() as usize)
+            .map(|rent| {
+                let share = (*rent as f64 * subsidy.rent_percent / 100.0).round() as i64;
+                if subsidy.rent_cap > 0 { share.min(subsidy.rent_cap as i64) } else { share }
+            })
+            .sum::<i64>();
+        let paid_items = e.cost_items()
+            .into_iter()
+            .filter(|kind| subsidy.paid_items.contains(kind))
+            .map(|kind| item_flows(e, kind).iter().sum::<i64>())
+            .sum::<i64>();
+        (e.over_stay(subsidy.allowance), rent_share as i32, paid_items as i32)
+    } else {
+        (0, 0, 0)
+    };
+    let employer_total = allowance_total + rent_share_total + paid_items_total;
+    Some(SubsidyResult {
+        eligible,
+        rent_limit: subsidy.rent_limit,
+        allowance_total,
+        rent_share_total,
+        paid_items_total,
+        employer_total,
+        net_total: total - employer_total,
+        net_monthly: e.per_month(total - employer_total),
+    })
+}
+
+/// Input of `kind` among the employer-paid items.
+pub fn paid_item_id(kind: ItemKind) -> String {
+    format!("{}_employer", kind.input_id())
+}
+
+/// Reads the checked employer-paid items, or `None` if they are not on the form.
+pub fn read_paid_items(document: &Document) -> Option<Vec<ItemKind>> {
+    document.get_element_by_id(&InputId::EmployerPaidItems.to_string())?;
+    Some(ItemKind::iter()
+        .filter(|kind| {
+            document
+                .get_element_by_id(&paid_item_id(*kind))
+                .and_then(|e| e.dyn_into::<HtmlInputElement>().ok())
+                .is_some_and(|i| i.checked())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::{RentCalculator, RentElements};
+    use crate::constants::ItemKind;
+    use crate::subsidy::{Subsidy, subsidize};
+
+    #[test]
+    fn subsidized() {
+        let e = RentElements::default();
+        assert_eq!(subsidize(&e, 1000000, 52000), None);
+
+        let e = RentElements {
+            subsidy: Subsidy {
+                allowance: 10000,
+                rent_percent: 50.0,
+                rent_cap: 20000,
+                paid_items: vec![ItemKind::KeyMoney, ItemKind::BrokerageFee],
+                rent_limit: 60000,
+            },
+            ..Default::default()
+        };
+        let r = RentCalculator {}.calc(e.clone()).unwrap();
+        let s = r.subsidy.unwrap();
+        assert!(s.eligible);
+        let months = r.lease_period;
+        assert_eq!(s.allowance_total, 10000 * months);
+        assert_eq!(s.rent_share_total, 20000 * months);
+        assert_eq!(s.paid_items_total, 50000 + 50000);
+        assert_eq!(s.net_total, r.total - s.employer_total);
+        assert!((r.monthly - s.net_monthly - s.employer_total / months).abs() <= 1);
+
+        let s = subsidize(&e, r.total, 60001).unwrap();
+        assert!(!s.eligible);
+        assert_eq!(s.net_total, r.total);
+    }
+}
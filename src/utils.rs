@@ -19,6 +19,11 @@ pub fn set_panic_hook() {
         console_error_panic_hook::set_once();
 }
 
+/// Converts a result for JS, with `None` as `null` like the TypeScript declarations say.
+pub fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::new().serialize_missing_as_null(true))
+}
+
 pub struct HtmlAttr<'a> {
     pub name: &'a str,
     pub value: &'a str,
@@ -28,9 +33,8 @@ pub fn make_tag(document: &Document, tag_name: &str,
                 attr: Vec<HtmlAttr>, inner: Option<&str>,
                 parent: Option<&Element>) -> Result<Element, JsValue> {
     let elem = document.create_element(tag_name)?;
-    attr.into_iter().map(|a|
-        elem.set_attribute(&a.name, &a.value)
-    ).collect::<Result<_, _>>()?;
+    attr.into_iter().try_for_each(|a|
+        elem.set_attribute(a.name, a.value))?;
     if let Some(i) = inner {
         elem.set_inner_html(i);
     }
@@ -42,6 +46,19 @@ pub fn make_tag(document: &Document, tag_name: &str,
 
 pub fn fetch_value<T>(document: &Document, id_key: InputId) -> Result<T, JsValue>
     where T: std::str::FromStr
+{
+    read_value(document, id_key, true)
+}
+
+/// Same as `fetch_value`, but does not show validation messages to the user.
+pub fn peek_value<T>(document: &Document, id_key: InputId) -> Result<T, JsValue>
+    where T: std::str::FromStr
+{
+    read_value(document, id_key, false)
+}
+
+fn read_value<T>(document: &Document, id_key: InputId, report: bool) -> Result<T, JsValue>
+    where T: std::str::FromStr
 {
     let id: &str = &id_key.to_string();
     let form_place_raw = document
@@ -50,13 +67,16 @@ pub fn fetch_value<T>(document: &Document, id_key: InputId) -> Result<T, JsValue
     let form_place = form_place_raw.dyn_ref::<HtmlElement>()
         .unwrap_or_else(|| panic!("{} is not a HtmlElement", id));
 
-    fn convert_specialized_element(form_place: &HtmlElement) -> Option<(String, bool)> {
-        form_place.dyn_ref::<HtmlInputElement>().map(|e| (e.value(), e.report_validity()))
-            .or_else(|| form_place.dyn_ref::<HtmlSelectElement>().map(|e| (e.value(), e.report_validity())))
-            .or_else(|| form_place.dyn_ref::<HtmlTextAreaElement>().map(|e| (e.value(), e.report_validity())))
+    fn convert_specialized_element(form_place: &HtmlElement, report: bool) -> Option<(String, bool)> {
+        form_place.dyn_ref::<HtmlInputElement>()
+            .map(|e| (e.value(), if report { e.report_validity() } else { e.check_validity() }))
+            .or_else(|| form_place.dyn_ref::<HtmlSelectElement>()
+                .map(|e| (e.value(), if report { e.report_validity() } else { e.check_validity() })))
+            .or_else(|| form_place.dyn_ref::<HtmlTextAreaElement>()
+                .map(|e| (e.value(), if report { e.report_validity() } else { e.check_validity() })))
     }
     let html_form =
-        convert_specialized_element(&form_place)
+        convert_specialized_element(form_place, report)
             .unwrap_or_else(|| panic!("{} is neither HtmlInputElement or HtmlSelectElement", id));
 
     let result = if html_form.1 {
@@ -0,0 +1,194 @@
+use std::convert::TryInto;
+use std::rc::Rc;
+
+use js_sys::{Array, Function, Reflect};
+use strum::IntoEnumIterator;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlElement;
+
+use crate::calculator::RentElements;
+use crate::HtmlError;
+use crate::constants::{InputId, ItemKind};
+use crate::i18n::Lang;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_MOUNT_OPTIONS: &'static str = r#"
+export interface MountOptions {
+    /** Items shown on the form. Hidden items keep their initial value. Defaults to all items. */
+    items?: ItemKind[];
+    /** Initial values, in the same shape as `Scenario.toJson()`. Missing fields use the defaults. */
+    initial?: object;
+    /** Defaults to true. */
+    header?: boolean;
+    /** Defaults to true. */
+    footer?: boolean;
+    /** Shows the changes at renewal. Defaults to true. */
+    schedule?: boolean;
+    /** Shows the move-in and move-out dates. Defaults to true. */
+    dates?: boolean;
+    /** Shows the area to paste listing text into. Defaults to true. */
+    listing?: boolean;
+    /** Shows the floor area and occupants, for the rent per ㎡, 畳 and person. Defaults to true. */
+    area?: boolean;
+    /** Shows the income, for an affordability report. Defaults to true. */
+    income?: boolean;
+    /** Shows the employer's housing allowance and corporate lease support. Defaults to true. */
+    subsidy?: boolean;
+    /** Shows the business share of the costs, for freelancers working from home. Defaults to false. */
+    homeOffice?: boolean;
+    /** Shows a purchase to compare renting with. Defaults to false. */
+    purchase?: boolean;
+    /** Shows how the people sharing the property split the costs. Defaults to true. */
+    split?: boolean;
+    /** Shows the apartment being left, whose rent overlaps with the new one. Defaults to true. */
+    currentHome?: boolean;
+    /** Shows the costs of moving in, such as movers and curtains. Defaults to true. */
+    moving?: boolean;
+    /** Shows utilities and internet, for the effective cost of living. Defaults to true. */
+    running?: boolean;
+    /** Shows the coming renewal of the current apartment, to decide between staying and moving. Defaults to false. */
+    renewal?: boolean;
+    /** Shows the discount rate used for the present value. Defaults to true. */
+    discount?: boolean;
+    /** Shows the JSON export/import area. Defaults to true. */
+    export?: boolean;
+    /** Recalculates on every input change. Can be switched on the form. Defaults to false. */
+    live?: boolean;
+    /** UI language. Defaults to the browser language. */
+    lang?: Lang;
+    /** Called with every new result. */
+    onResult?: (result: RentResult) => void;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "MountOptions")]
+    pub type JsMountOptions;
+}
+
+#[derive(Clone)]
+pub struct MountOptions {
+    pub items: Vec<ItemKind>,
+    pub initial: RentElements,
+    pub header: bool,
+    pub footer: bool,
+    pub schedule: bool,
+    pub dates: bool,
+    pub area: bool,
+    pub income: bool,
+    pub subsidy: bool,
+    pub home_office: bool,
+    pub purchase: bool,
+    pub split: bool,
+    pub current_home: bool,
+    pub moving: bool,
+    pub running: bool,
+    pub renewal: bool,
+    pub discount: bool,
+    pub listing: bool,
+    pub export: bool,
+    pub live: bool,
+    pub lang: Lang,
+    pub on_result: Option<Function>,
+}
+
+impl Default for MountOptions {
+    fn default() -> Self {
+        Self {
+            items: ItemKind::iter().collect(),
+            initial: RentElements::default(),
+            header: true,
+            footer: true,
+            schedule: true,
+            dates: true,
+            area: true,
+            income: true,
+            subsidy: true,
+            home_office: false,
+            purchase: false,
+            split: true,
+            current_home: true,
+            moving: true,
+            running: true,
+            renewal: false,
+            discount: true,
+            listing: true,
+            export: true,
+            live: false,
+            lang: Lang::detect(),
+            on_result: None,
+        }
+    }
+}
+
+impl MountOptions {
+    fn from_js(options: &JsValue) -> Result<Self, JsValue> {
+        let mut o = MountOptions::default();
+        if options.is_undefined() || options.is_null() {
+            return Ok(o);
+        }
+
+        let get = |key: &str| Reflect::get(options, &JsValue::from_str(key));
+
+        let items = get("items")?;
+        if !items.is_undefined() {
+            o.items = items
+                .dyn_into::<Array>()?
+                .iter()
+                .map(|v| (v.as_f64().unwrap_or(-1.0) as i32).try_into())
+                .collect::<Result<_, _>>()?;
+        }
+        let initial = get("initial")?;
+        if !initial.is_undefined() {
+            o.initial = serde_wasm_bindgen::from_value(initial)?;
+        }
+        o.header = get("header")?.as_bool().unwrap_or(o.header);
+        o.footer = get("footer")?.as_bool().unwrap_or(o.footer);
+        o.schedule = get("schedule")?.as_bool().unwrap_or(o.schedule);
+        o.dates = get("dates")?.as_bool().unwrap_or(o.dates);
+        o.area = get("area")?.as_bool().unwrap_or(o.area);
+        o.income = get("income")?.as_bool().unwrap_or(o.income);
+        o.subsidy = get("subsidy")?.as_bool().unwrap_or(o.subsidy);
+        o.home_office = get("homeOffice")?.as_bool().unwrap_or(o.home_office);
+        o.purchase = get("purchase")?.as_bool().unwrap_or(o.purchase);
+        o.split = get("split")?.as_bool().unwrap_or(o.split);
+        o.current_home = get("currentHome")?.as_bool().unwrap_or(o.current_home);
+        o.moving = get("moving")?.as_bool().unwrap_or(o.moving);
+        o.running = get("running")?.as_bool().unwrap_or(o.running);
+        o.renewal = get("renewal")?.as_bool().unwrap_or(o.renewal);
+        o.discount = get("discount")?.as_bool().unwrap_or(o.discount);
+        o.listing = get("listing")?.as_bool().unwrap_or(o.listing);
+        o.export = get("export")?.as_bool().unwrap_or(o.export);
+        o.live = get("live")?.as_bool().unwrap_or(o.live);
+        let lang = get("lang")?;
+        if let Some(l) = lang.as_f64() {
+            o.lang = Lang::ALL
+                .iter()
+                .copied()
+                .find(|x| *x as i32 == l as i32)
+                .ok_or_else(|| JsValue::from_str("Invalid integer for Lang"))?;
+        }
+        let on_result = get("onResult")?;
+        if !on_result.is_undefined() {
+            o.on_result = Some(on_result.dyn_into::<Function>()?);
+        }
+        Ok(o)
+    }
+}
+
+/// Builds the calculator UI into `target`.
+/// Element ids are fixed, so mounting a second calculator on the same page is an error.
+#[wasm_bindgen]
+pub fn mount(target: &HtmlElement, options: Option<JsMountOptions>) -> Result<(), JsValue> {
+    let options = match options {
+        Some(o) => MountOptions::from_js(&o)?,
+        None => MountOptions::default(),
+    };
+    let document = target.owner_document().ok_or(HtmlError::NotInDocument)?;
+    if document.get_element_by_id(&InputId::CalcResultTitle.to_string()).is_some() {
+        return Err(HtmlError::AlreadyMounted.into());
+    }
+    crate::construct(&document, target, Rc::new(options)).map(|_| ())
+}
@@ -0,0 +1,152 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::InputError;
+
+/// Yen amount read with `parse_yen`, for use with `fetch_value`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Yen(pub i32);
+
+impl FromStr for Yen {
+    type Err = InputError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_yen(s)
+            .map(Yen)
+            .ok_or_else(|| InputError::CannotParse(s.to_string()))
+    }
+}
+
+pub fn to_half_width(c: char) -> char {
+    match c {
+        '０'..='９' => std::char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+        '．' => '.',
+        '，' | '、' => ',',
+        '￥' | '\\' => '¥',
+        _ => c,
+    }
+}
+
+/// `num` times `multiplier`, as long as the result is a whole yen.
+fn to_amount(num: &str, multiplier: i64) -> Option<i64> {
+    let v = num.parse::<f64>().ok()? * multiplier as f64;
+    if (v - v.round()).abs() > 1e-6 || v > i32::MAX as f64 {
+        return None;
+    }
+    Some(v.round() as i64)
+}
+
+/// Reads yen amounts as written on listing sites:
+/// `52000`, `52,000`, `¥52,000`, `5.2万`, `5万2千円`, and their full-width forms.
+pub fn parse_yen(text: &str) -> Option<i32> {
+    let s: String = text
+        .chars()
+        .map(to_half_width)
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect();
+    let s = s.strip_prefix('¥').unwrap_or(&s);
+    let s = s.strip_suffix('円').unwrap_or(s);
+    if s.is_empty() {
+        return None;
+    }
+
+    // each segment such as `5万` must be smaller than the previous multiplier
+    let mut total = 0;
+    let mut upper = i64::MAX;
+    let mut num = String::new();
+    for c in s.chars() {
+        let multiplier = match c {
+            '0'..='9' | '.' => {
+                num.push(c);
+                continue;
+            }
+            '万' => 10_000,
+            '千' => 1_000,
+            _ => return None,
+        };
+        let amount = to_amount(&num, multiplier)?;
+        if num.is_empty() || amount >= upper {
+            return None;
+        }
+        total += amount;
+        upper = multiplier;
+        num.clear();
+    }
+    if !num.is_empty() {
+        let amount = to_amount(&num, 1)?;
+        if amount >= upper {
+            return None;
+        }
+        total += amount;
+    }
+    i32::try_from(total).ok()
+}
+
+/// `52000` -> `52,000`
+pub fn format_yen(value: i32) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if value < 0 {
+        format!("-{}", out)
+    } else {
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::yen::{format_yen, parse_yen};
+
+    #[test]
+    fn plain() {
+        assert_eq!(parse_yen("52000"), Some(52000));
+        assert_eq!(parse_yen("52,000"), Some(52000));
+        assert_eq!(parse_yen("¥52,000"), Some(52000));
+        assert_eq!(parse_yen(" 52000円 "), Some(52000));
+        assert_eq!(parse_yen("0"), Some(0));
+    }
+
+    #[test]
+    fn full_width() {
+        assert_eq!(parse_yen("５２０００"), Some(52000));
+        assert_eq!(parse_yen("￥５２，０００"), Some(52000));
+        assert_eq!(parse_yen("５．２万円"), Some(52000));
+    }
+
+    #[test]
+    fn multipliers() {
+        assert_eq!(parse_yen("5.2万"), Some(52000));
+        assert_eq!(parse_yen("8.5万円"), Some(85000));
+        assert_eq!(parse_yen("5万2千円"), Some(52000));
+        assert_eq!(parse_yen("1万2,000円"), Some(12000));
+        assert_eq!(parse_yen("3千"), Some(3000));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(parse_yen(""), None);
+        assert_eq!(parse_yen("円"), None);
+        assert_eq!(parse_yen("abc"), None);
+        assert_eq!(parse_yen("-5000"), None);
+        assert_eq!(parse_yen("520.5"), None);
+        assert_eq!(parse_yen("5千3万"), None);
+        assert_eq!(parse_yen("5万20000"), None);
+        assert_eq!(parse_yen("万"), None);
+        assert_eq!(parse_yen("99999999999"), None);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_yen(0), "0");
+        assert_eq!(format_yen(999), "999");
+        assert_eq!(format_yen(52000), "52,000");
+        assert_eq!(format_yen(1234567), "1,234,567");
+        assert_eq!(format_yen(-52000), "-52,000");
+        assert_eq!(format_yen(i32::MIN), "-2,147,483,648");
+    }
+}
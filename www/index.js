@@ -1 +1,3 @@
 import * as wasm from "wasm-real-rent";
+
+wasm.run();